@@ -0,0 +1,338 @@
+//! Derive macros for the `AbsError` and `RelError` traits of `appro-eq`.
+//!
+//! # Overview
+//! The derived impls compare each field with its own impl and fold the errors into the largest one,
+//! as slices do. Enable the `derive` feature of `appro-eq` rather than depending on this crate directly.
+//!
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Expr, Fields, GenericParam, Generics, Ident,
+    Member, Result, Type,
+};
+
+/// Derives `AbsError<Self, D>` for every `D` all the compared fields have an `AbsError` for.
+///
+/// See the crate documentation of `appro-eq` for the field attributes.
+#[proc_macro_derive(AbsError, attributes(appro_eq))]
+pub fn derive_abs_error(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), Kind::Abs)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives `RelError<Self, D>` for every `D` all the compared fields have a `RelError` for.
+///
+/// See the crate documentation of `appro-eq` for the field attributes.
+#[proc_macro_derive(RelError, attributes(appro_eq))]
+pub fn derive_rel_error(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), Kind::Rel)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Abs,
+    Rel,
+}
+
+/// How a field is compared, from its `#[appro_eq(...)]` attributes.
+enum Mode {
+    /// With the impl of the field type, folded into the error of the whole.
+    Error,
+    /// Not at all.
+    Skip,
+    /// With `PartialEq`.
+    Exact,
+    /// With the impl of the field type, against its own tolerance.
+    Tolerance(Expr),
+}
+
+struct Field {
+    member: Member,
+    name: String,
+    ty: Type,
+    mode: Mode,
+}
+
+impl Field {
+    fn parse(fields: &Fields, kind: Kind) -> Result<Vec<Field>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let (member, name) = match &field.ident {
+                    Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+                    None => (Member::Unnamed(i.into()), i.to_string()),
+                };
+                let mut mode = Mode::Error;
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("appro_eq")) {
+                    attr.parse_nested_meta(|meta| {
+                        let next = if meta.path.is_ident("skip") {
+                            Some(Mode::Skip)
+                        } else if meta.path.is_ident("exact") {
+                            Some(Mode::Exact)
+                        } else if meta.path.is_ident("abs") {
+                            let tol = meta.value()?.parse::<Expr>()?;
+                            Some(Mode::Tolerance(tol)).filter(|_| kind == Kind::Abs)
+                        } else if meta.path.is_ident("rel") {
+                            let tol = meta.value()?.parse::<Expr>()?;
+                            Some(Mode::Tolerance(tol)).filter(|_| kind == Kind::Rel)
+                        } else {
+                            return Err(
+                                meta.error("expected `skip`, `exact`, `abs = ...` or `rel = ...`")
+                            );
+                        };
+                        if let Some(next) = next {
+                            if !matches!(mode, Mode::Error) {
+                                return Err(meta.error("conflicting `appro_eq` attributes"));
+                            }
+                            mode = next;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(Field {
+                    member,
+                    name,
+                    ty: field.ty.clone(),
+                    mode,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Generates the comparison of the fields bound to `__actual_<i>` and `__expected_<i>`,
+/// with the patterns binding them.
+///
+/// A field with its own tolerance is checked by a nested function, out of reach of the bounds of the impl,
+/// which would otherwise make the type of the tolerance that of the impl. The type of such a field may not
+/// have type parameters, as nothing would bound them to compare with the tolerance.
+fn compare(
+    path: TokenStream,
+    fields: &[Field],
+    kind: Kind,
+    generics: &Generics,
+) -> Result<TokenStream> {
+    let mut actual = Vec::new();
+    let mut expected = Vec::new();
+    let mut compared = Vec::new();
+    let mut checks = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        if let Mode::Skip = field.mode {
+            continue;
+        }
+        let member = &field.member;
+        let a = format_ident!("__actual_{}", i);
+        let e = format_ident!("__expected_{}", i);
+        actual.push(quote!(#member: #a));
+        expected.push(quote!(#member: #e));
+        let name = &field.name;
+        let error = match (&field.mode, kind) {
+            (Mode::Exact, _) => quote! {
+                ::appro_eq::__support::visit(::appro_eq::__support::exact(#a, #e), path, visitor)
+            },
+            (Mode::Tolerance(tol), _) => {
+                let ty = &field.ty;
+                let check = format_ident!("__check_{}", i);
+                let check_generics = check_generics(ty, generics)?;
+                let (impl_generics, _, _) = check_generics.split_for_impl();
+                let (def, def_arg, call) = match kind {
+                    Kind::Abs => (
+                        quote!(),
+                        quote!(),
+                        quote!(::appro_eq::abs_appro_check(actual, expected, &(#tol))),
+                    ),
+                    Kind::Rel => (
+                        quote!(def: ::appro_eq::RelErrorDef),
+                        quote!(def),
+                        quote!(::appro_eq::rel_appro_check_with_def(actual, expected, &(#tol), def)),
+                    ),
+                };
+                checks.push(quote! {
+                    fn #check #impl_generics(actual: &#ty, expected: &#ty, #def)
+                        -> ::core::result::Result<(), ::appro_eq::ApproEqError>
+                    {
+                        ::appro_eq::__support::field_check(#call)
+                    }
+                });
+                quote! {
+                    ::appro_eq::__support::visit(
+                        #check(#a, #e, #def_arg).map(|()| ::core::option::Option::None),
+                        path,
+                        visitor,
+                    )
+                }
+            }
+            (_, Kind::Abs) => quote!(::appro_eq::AbsError::abs_visit(#a, #e, path, visitor)),
+            (_, Kind::Rel) => quote!(::appro_eq::RelError::rel_visit(#a, #e, def, path, visitor)),
+        };
+        compared.push(quote!((#name, &|path, visitor| #error)));
+    }
+    Ok(quote! {
+        (#path { #(#actual,)* .. }, #path { #(#expected,)* .. }) => {
+            #(#checks)*
+            ::appro_eq::__support::fields(path, visitor, &[#(#compared),*])
+        }
+    })
+}
+
+/// The generics of the function checking a field of type `ty` against its own tolerance:
+/// the lifetimes, and the const parameters `ty` uses so that they are inferred from the arguments.
+fn check_generics(ty: &Type, generics: &Generics) -> Result<Generics> {
+    let mut check = Generics::default();
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(_) => check.params.push(param.clone()),
+            GenericParam::Const(param) if mentions(quote!(#ty), &param.ident) => {
+                check.params.push(GenericParam::Const(param.clone()))
+            }
+            GenericParam::Type(param) if mentions(quote!(#ty), &param.ident) => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "`abs` and `rel` cannot be given for a field whose type has the type parameter `{}`",
+                        param.ident
+                    ),
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(check)
+}
+
+/// Tells whether `tokens` contain `ident`.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(token) => token == *ident,
+        TokenTree::Group(group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+fn expand(input: DeriveInput, kind: Kind) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (arms, fields) = match &input.data {
+        Data::Struct(data) => {
+            let fields = Field::parse(&data.fields, kind)?;
+            (
+                vec![compare(quote!(Self), &fields, kind, &input.generics)?],
+                fields,
+            )
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            let mut all = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let fields = Field::parse(&variant.fields, kind)?;
+                arms.push(compare(
+                    quote!(Self::#ident),
+                    &fields,
+                    kind,
+                    &input.generics,
+                )?);
+                all.extend(fields);
+            }
+            (arms, all)
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "unions cannot be compared field by field",
+            ))
+        }
+    };
+
+    let diff = Ident::new("__D", Span::call_site());
+    let result = quote!(::core::result::Result<::core::option::Option<#diff>, ::appro_eq::ApproEqError>);
+    let visited = quote!(::core::result::Result<(), ::appro_eq::ApproEqError>);
+    let visit_args = quote! {
+        path: &::appro_eq::__support::Path<'_>,
+        visitor: &mut dyn ::appro_eq::__support::Visitor<#diff>
+    };
+    let (trait_name, method, visit, default) = match kind {
+        Kind::Abs => (
+            quote!(::appro_eq::AbsError),
+            quote! {
+                fn abs_error(&self, expected: &Self) -> #result {
+                    ::appro_eq::__support::reduce(|path, visitor| {
+                        ::appro_eq::AbsError::abs_visit(self, expected, path, visitor)
+                    })
+                }
+            },
+            quote!(fn abs_visit(&self, expected: &Self, #visit_args)),
+            quote!(),
+        ),
+        Kind::Rel => (
+            quote!(::appro_eq::RelError),
+            quote! {
+                fn rel_error_with_def(&self, expected: &Self, def: ::appro_eq::RelErrorDef) -> #result {
+                    ::appro_eq::__support::reduce(|path, visitor| {
+                        ::appro_eq::RelError::rel_visit(self, expected, def, path, visitor)
+                    })
+                }
+            },
+            quote!(fn rel_visit(&self, expected: &Self, def: ::appro_eq::RelErrorDef, #visit_args)),
+            quote! {
+                fn rel_error(&self, expected: &Self) -> #result {
+                    ::appro_eq::RelError::<Self, #diff>::rel_error_with_def(
+                        self,
+                        expected,
+                        ::appro_eq::RelErrorDef::default(),
+                    )
+                }
+            },
+        ),
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(#diff: ::core::cmp::PartialOrd));
+    let where_clause = generics.make_where_clause();
+    for field in &fields {
+        let ty = &field.ty;
+        match field.mode {
+            Mode::Error => where_clause
+                .predicates
+                .push(parse_quote!(#ty: #trait_name<#ty, #diff>)),
+            Mode::Exact => where_clause
+                .predicates
+                .push(parse_quote!(#ty: ::core::cmp::PartialEq)),
+            Mode::Skip | Mode::Tolerance(_) => {}
+        }
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #trait_name<Self, #diff> for #name #ty_generics #where_clause {
+            #default
+
+            #method
+
+            #[allow(unused_variables)]
+            #visit -> #visited {
+                #[allow(unreachable_patterns)]
+                match (self, expected) {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(
+                        path.locate(::appro_eq::ApproEqError::VariantMismatch),
+                    ),
+                }
+            }
+        }
+    })
+}
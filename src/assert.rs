@@ -7,6 +7,12 @@
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// Instead of a diff value, `tol = <Tol>` or `tols = <tolerances>` can be given, and the `outliers`, `norm`,
+/// `policy` and `msg` options can follow, as described in [Assertion options](crate#assertion-options).
+///
+/// On failure, the message reports the largest error and the tolerance, the index or coordinate
+/// of the element it was found at and the number of elements exceeding the tolerance, or the reason
+/// the error could not be measured, such as a length mismatch. Long values are truncated.
 ///
 /// # Examples
 ///
@@ -14,6 +20,9 @@
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// assert_appro_eq!(1f64, 1.5f64, 0.6f64); // does not panic
+/// assert_appro_eq!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_eq!(1f64, 1.5f64, 0.6f64, "seed: {}", 42); // does not panic
+/// assert_appro_eq!(1f64, 1.5f64, tol = appro_eq::Tol::new().rel(0.5)); // does not panic
 /// assert_appro_eq!(0f64, 1e-12f64); // does not panic
 /// assert_appro_eq!(vec![0f64, 1.0, 0.0], vec![1e-12f64, 1.0, -1e-13f64]); // does not panic
 /// # }
@@ -27,22 +36,128 @@
 #[macro_export]
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 macro_rules! assert_appro_eq {
+    ($a:expr, $b:expr, tol = $tol:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Outliers::of($outliers, &$a), $b, tol = $tol $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Outliers::of($outliers, &$a), $b, tols = $tols $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Norm::of($norm, &$a), $b, tol = $tol $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Norm::of($norm, &$a), $b, tols = $tols $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::tol_appro_check(a, b, &$tol) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq!($a, $b, tol = $tol, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::tols_appro_check(a, b, &$tols) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq!($a, $b, tols = $tols, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::tol_appro_check(a, b, &$tol) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::tols_appro_check(a, b, &$tols) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
     ($a:expr, $b:expr) => {{
-        assert!(
-            $crate::AbsApproEq::abs_appro_eq(&$a, &$b),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`)",
-            $a,
-            $b
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, None);
+                }
+            }
+        }
     }};
     ($a:expr, $b:expr, $eps:expr) => {{
-        assert!(
-            $crate::AbsApproEqWithTol::abs_appro_eq_with_tol(&$a, &$b, &$eps),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
-            $a,
-            $b,
-            $eps
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
     }};
 }
 
@@ -50,6 +165,8 @@ macro_rules! assert_appro_eq {
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
 ///
 /// # Examples
 ///
@@ -57,6 +174,8 @@ macro_rules! assert_appro_eq {
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// assert_appro_eq_abs!(1f64, 1.5f64, 0.6f64); // does not panic
+/// assert_appro_eq_abs!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_eq_abs!(1f64, 1.5f64, 0.6f64, "seed: {}", 42); // does not panic
 /// assert_appro_eq_abs!(0f64, 1e-12f64); // does not panic
 /// assert_appro_eq_abs!(vec![0f64, 1.0, 0.0], vec![1e-12f64, 1.0, -1e-13f64]); // does not panic
 /// # }
@@ -70,22 +189,74 @@ macro_rules! assert_appro_eq {
 #[macro_export]
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 macro_rules! assert_appro_eq_abs {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_abs!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_abs!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_abs!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_abs!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_abs!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_abs!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_abs!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_abs!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_abs!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_abs!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_abs!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
     ($a:expr, $b:expr) => {{
-        assert!(
-            $crate::AbsApproEq::abs_appro_eq(&$a, &$b),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`)",
-            $a,
-            $b
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, None);
+                }
+            }
+        }
     }};
     ($a:expr, $b:expr, $eps:expr) => {{
-        assert!(
-            $crate::AbsApproEqWithTol::abs_appro_eq_with_tol(&$a, &$b, &$eps),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
-            $a,
-            $b,
-            $eps
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
     }};
 }
 
@@ -93,6 +264,13 @@ macro_rules! assert_appro_eq_abs {
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+/// The diff value can be followed by a `RelErrorDef` selecting the definition of the relative error.
+/// Without a diff value, values that have an `f64` relative error, such as integers, `Duration`s and collections
+/// of them, are compared by it. A diff value of an integer type compares integers by their relative error of that
+/// type, rounded up, and a `Duration` compares `Duration`s by their relative error counting a ratio of 1 as one
+/// second.
 ///
 /// # Examples
 ///
@@ -100,8 +278,12 @@ macro_rules! assert_appro_eq_abs {
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// assert_appro_eq_rel!(1f64, 1.5f64, 0.6f64); // does not panic
+/// assert_appro_eq_rel!(1000u32, 990u32, 0.011); // does not panic
+/// assert_appro_eq_rel!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_eq_rel!(1f64, 1.5f64, 0.6f64, "seed: {}", 42); // does not panic
 /// assert_appro_eq_rel!(1f64, 1.0 + 1e-12f64); // does not panic
 /// assert_appro_eq_rel!(vec![1f64, 1.0, 1.0], vec![1.0 + 1e-12f64, 1.0, 1.0 - 1e-13f64]); // does not panic
+/// assert_appro_eq_rel!(0f64, 1e-12f64, 1.0, appro_eq::RelErrorDef::Max); // does not panic
 /// # }
 /// ```
 /// ```should_panic
@@ -110,25 +292,320 @@ macro_rules! assert_appro_eq_abs {
 /// assert_appro_eq_rel!(1f64, 2f64); // panics
 /// # }
 /// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_eq_rel!(1000u32, 999u32); // panics
+/// # }
+/// ```
 #[macro_export]
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 macro_rules! assert_appro_eq_rel {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Outliers::of($outliers, &$a), $b, $eps, $def $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_rel!($crate::Norm::of($norm, &$a), $b, $eps, $def $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = {
+                    use $crate::__support::{RelCheckDiff as _, RelCheckOwn as _, RelCheckRatio as _};
+                    (&&&$crate::__support::RelCheck(a, b)).rel_appro_check()
+                } {
+                    $crate::__support::fail(a, b, &mismatch, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_rel!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_rel!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b, $eps, $def, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_rel!($a, $b, $eps, $def, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_rel!($a, $b, $eps, $def));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::rel_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_rel!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::rel_appro_check_with_def(a, b, &$eps, $def) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_rel!($a, $b, $eps, $def, msg = $fmt $($args)*);
+    }};
     ($a:expr, $b:expr) => {{
-        assert!(
-            $crate::RelApproEq::rel_appro_eq(&$a, &$b),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`)",
-            $a,
-            $b
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = {
+                    use $crate::__support::{RelCheckDiff as _, RelCheckOwn as _, RelCheckRatio as _};
+                    (&&&$crate::__support::RelCheck(a, b)).rel_appro_check()
+                } {
+                    $crate::__support::fail(a, b, &mismatch, true, None);
+                }
+            }
+        }
     }};
     ($a:expr, $b:expr, $eps:expr) => {{
-        assert!(
-            $crate::RelApproEqWithTol::rel_appro_eq_with_tol(&$a, &$b, &$eps),
-            "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
-            $a,
-            $b,
-            $eps
-        );
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::rel_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::rel_appro_check_with_def(a, b, &$eps, $def) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
+    }};
+}
+
+/// Asserts that the two expressions are within a number of units in the last place of each other.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, UlpsTolerance::ulps_tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_eq_ulps!(1f64, 1f64 + 3.0 * f64::EPSILON, 4u64); // does not panic
+/// assert_appro_eq_ulps!(1f64, 1f64 + f64::EPSILON); // does not panic
+/// assert_appro_eq_ulps!(vec![-0f64, 1.0, 1e300], vec![0f64, 1.0, 1e300]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_eq_ulps!(1f64, 1f64 + 1e-12); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! assert_appro_eq_ulps {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_ulps!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_ulps!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_ulps!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_ulps!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::ulps_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_ulps!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_ulps!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_ulps!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_ulps!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_ulps!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_ulps!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::ulps_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_ulps!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::ulps_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::ulps_appro_check(a, b, &$eps) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
+    }};
+}
+
+/// Asserts that the absolute error of the two expressions is within the absolute tolerance or
+/// the relative tolerance times the larger magnitude.
+///
+/// You can optionally add an optional pair of diff values(absolute and relative). If you don't supply
+///  them as arguments, AbsTolerance::abs_tolerance() and RelTolerance::rel_tolerance() are the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_eq_mixed!(0f64, 1e-12f64, 1e-9f64, 1e-6f64); // does not panic
+/// assert_appro_eq_mixed!(1e6f64, 1e6f64 + 0.5, 1e-9f64, 1e-6f64); // does not panic
+/// assert_appro_eq_mixed!(vec![0f64, 1.0, 1e6], vec![1e-12f64, 1.0, 1e6 + 1e-6]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_eq_mixed!(0f64, 1e-3f64, 1e-9f64, 1e-6f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! assert_appro_eq_mixed {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_mixed!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_mixed!($crate::Outliers::of($outliers, &$a), $b, $abs, $rel $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_mixed!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_eq_mixed!($crate::Norm::of($norm, &$a), $b, $abs, $rel $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::mixed_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_mixed!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_mixed!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_mixed!($a, $b));
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_mixed!($a, $b, $abs, $rel, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_mixed!($a, $b, $abs, $rel, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_eq_mixed!($a, $b, $abs, $rel));
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::mixed_appro_check(a, b, &$abs, &$rel) {
+                    $crate::__support::fail(a, b, &mismatch, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_eq_mixed!($a, $b, $abs, $rel, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::__support::mixed_appro_check(a, b) {
+                    $crate::__support::fail(a, b, &mismatch, true, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $abs:expr, $rel:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Err(mismatch) = $crate::mixed_appro_check(a, b, &$abs, &$rel) {
+                    $crate::__support::fail(a, b, &mismatch, false, None);
+                }
+            }
+        }
     }};
 }
 
@@ -136,6 +613,8 @@ macro_rules! assert_appro_eq_rel {
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// Instead of a diff value, `tol = <Tol>` or `tols = <tolerances>` can be given, and the `outliers`, `norm`,
+/// `policy` and `msg` options can follow, as described in [Assertion options](crate#assertion-options).
 ///
 /// Unlike assert_appro_eq!, debug_assert_appro_eq! statements are only enabled in non optimized builds by default.
 /// An optimized build will omit all debug_assert_appro_eq! statements unless -C debug-assertions is passed to the compiler.
@@ -146,6 +625,8 @@ macro_rules! assert_appro_eq_rel {
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// debug_assert_appro_eq!(1f64, 1.5f64, 0.6f64); // does not panic
+/// debug_assert_appro_eq!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
+/// debug_assert_appro_eq!(1f64, 1.5f64, tol = appro_eq::Tol::new().rel(0.5)); // does not panic
 /// debug_assert_appro_eq!(0f64, 1e-12f64); // does not panic
 /// debug_assert_appro_eq!(vec![0f64, 1.0, 0.0], vec![1e-12f64, 1.0, -1e-13f64]); // does not panic
 /// # }
@@ -166,6 +647,8 @@ macro_rules! debug_assert_appro_eq {
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
 ///
 /// Unlike assert_appro_eq_abs!, debug_assert_appro_eq_abs! statements are only enabled in non optimized builds by default.
 /// An optimized build will omit all debug_assert_appro_eq_abs! statements unless -C debug-assertions is passed to the compiler.
@@ -176,6 +659,7 @@ macro_rules! debug_assert_appro_eq {
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// debug_assert_appro_eq_abs!(1f64, 1.5f64, 0.6f64); // does not panic
+/// debug_assert_appro_eq_abs!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
 /// debug_assert_appro_eq_abs!(0f64, 1e-12f64); // does not panic
 /// debug_assert_appro_eq_abs!(vec![0f64, 1.0, 0.0], vec![1e-12f64, 1.0, -1e-13f64]); // does not panic
 /// # }
@@ -196,6 +680,9 @@ macro_rules! debug_assert_appro_eq_abs {
 ///
 /// You can optionally add an optional diff value. If you don't supply
 ///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+/// The diff value can be followed by a `RelErrorDef` selecting the definition of the relative error.
 ///
 /// Unlike assert_appro_eq_rel!, debug_assert_appro_eq_rel! statements are only enabled in non optimized builds by default.
 /// An optimized build will omit all debug_assert_appro_eq_rel! statements unless -C debug-assertions is passed to the compiler.
@@ -206,8 +693,10 @@ macro_rules! debug_assert_appro_eq_abs {
 /// # #[macro_use] extern crate appro_eq;
 /// # fn main() {
 /// debug_assert_appro_eq_rel!(1f64, 1.5f64, 0.6f64); // does not panic
+/// debug_assert_appro_eq_rel!(1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42); // does not panic
 /// debug_assert_appro_eq_rel!(1f64, 1.0 + 1e-12f64); // does not panic
 /// debug_assert_appro_eq_rel!(vec![1f64, 1.0, 1.0], vec![1.0 + 1e-12f64, 1.0, 1.0 - 1e-13f64]); // does not panic
+/// debug_assert_appro_eq_rel!(0f64, 1e-12f64, 1.0, appro_eq::RelErrorDef::Max); // does not panic
 /// # }
 /// ```
 /// ```should_panic
@@ -221,3 +710,580 @@ macro_rules! debug_assert_appro_eq_abs {
 macro_rules! debug_assert_appro_eq_rel {
     ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_eq_rel!($($arg)*); })
 }
+
+/// Asserts that the two expressions are within a number of units in the last place of each other.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, UlpsTolerance::ulps_tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// Unlike assert_appro_eq_ulps!, debug_assert_appro_eq_ulps! statements are only enabled in non optimized builds by default.
+/// An optimized build will omit all debug_assert_appro_eq_ulps! statements unless -C debug-assertions is passed to the compiler.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_eq_ulps!(1f64, 1f64 + 3.0 * f64::EPSILON, 4u64); // does not panic
+/// debug_assert_appro_eq_ulps!(1f64, 1f64 + f64::EPSILON); // does not panic
+/// debug_assert_appro_eq_ulps!(vec![-0f64, 1.0, 1e300], vec![0f64, 1.0, 1e300]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_eq_ulps!(1f64, 1f64 + 1e-12); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! debug_assert_appro_eq_ulps {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_eq_ulps!($($arg)*); })
+}
+
+/// Asserts that the absolute error of the two expressions is within the absolute tolerance or
+/// the relative tolerance times the larger magnitude.
+///
+/// You can optionally add an optional pair of diff values(absolute and relative). If you don't supply
+///  them as arguments, AbsTolerance::abs_tolerance() and RelTolerance::rel_tolerance() are the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// Unlike assert_appro_eq_mixed!, debug_assert_appro_eq_mixed! statements are only enabled in non optimized builds by default.
+/// An optimized build will omit all debug_assert_appro_eq_mixed! statements unless -C debug-assertions is passed to the compiler.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_eq_mixed!(0f64, 1e-12f64, 1e-9f64, 1e-6f64); // does not panic
+/// debug_assert_appro_eq_mixed!(1e6f64, 1e6f64 + 0.5, 1e-9f64, 1e-6f64); // does not panic
+/// debug_assert_appro_eq_mixed!(vec![0f64, 1.0, 1e6], vec![1e-12f64, 1.0, 1e6 + 1e-6]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_eq_mixed!(0f64, 1e-3f64, 1e-9f64, 1e-6f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! debug_assert_appro_eq_mixed {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_eq_mixed!($($arg)*); })
+}
+
+/// Asserts that two expressions are not approximately equal to each other.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// Instead of a diff value, `tol = <Tol>` or `tols = <tolerances>` can be given, and the `outliers`, `norm`,
+/// `policy` and `msg` options can follow, as described in [Assertion options](crate#assertion-options).
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne!(1f64, 1.5f64, 0.4f64); // does not panic
+/// assert_appro_ne!(1f64, 1.5f64, 0.4f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_ne!(1f64, 1.5f64, 0.4f64, "seed: {}", 42); // does not panic
+/// assert_appro_ne!(1f64, 1.5f64, tol = appro_eq::Tol::new().rel(0.3)); // does not panic
+/// assert_appro_ne!(0f64, 1e-10f64); // does not panic
+/// assert_appro_ne!(vec![0f64, 1.0], vec![0f64, 1.0, 2.0]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne!(1f64, 1.5f64, 0.6f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! assert_appro_ne {
+    ($a:expr, $b:expr, tol = $tol:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Outliers::of($outliers, &$a), $b, tol = $tol $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Outliers::of($outliers, &$a), $b, tols = $tols $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Norm::of($norm, &$a), $b, tol = $tol $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Norm::of($norm, &$a), $b, tols = $tols $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::tol_appro_check(a, b, &$tol) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne!($a, $b, tol = $tol, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::tols_appro_check(a, b, &$tols) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne!($a, $b, tols = $tols, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail_ne(a, b, &report, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, tol = $tol:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::tol_appro_check(a, b, &$tol) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, tols = $tols:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::tols_appro_check(a, b, &$tols) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail_ne(a, b, &report, true, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+}
+
+/// Asserts that two expressions are not approximately equal to each other.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// Instead of a diff value, `tol = <Tol>` or `tols = <tolerances>` can be given, and the `outliers`, `norm`,
+/// `policy` and `msg` options can follow, as described in [Assertion options](crate#assertion-options).
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// Unlike assert_appro_ne!, debug_assert_appro_ne! statements are only enabled in non optimized builds by default.
+/// An optimized build will omit all debug_assert_appro_ne! statements unless -C debug-assertions is passed to the compiler.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne!(1f64, 1.5f64, 0.4f64); // does not panic
+/// debug_assert_appro_ne!(1f64, 1.5f64, 0.4f64, msg = "seed: {}", 42); // does not panic
+/// debug_assert_appro_ne!(1f64, 1.5f64, tol = appro_eq::Tol::new().rel(0.3)); // does not panic
+/// debug_assert_appro_ne!(0f64, 1e-10f64); // does not panic
+/// debug_assert_appro_ne!(vec![0f64, 1.0], vec![0f64, 1.0, 2.0]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne!(1f64, 1.5f64, 0.6f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! debug_assert_appro_ne {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_ne!($($arg)*); })
+}
+
+/// Asserts that the absolute error of the two expressions is not small enough.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne_abs!(1f64, 1.5f64, 0.4f64); // does not panic
+/// assert_appro_ne_abs!(1f64, 1.5f64, 0.4f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_ne_abs!(1f64, 1.5f64, 0.4f64, "seed: {}", 42); // does not panic
+/// assert_appro_ne_abs!(0f64, 1e-10f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne_abs!(0f64, 1e-12f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! assert_appro_ne_abs {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_abs!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_abs!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_abs!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_abs!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail_ne(a, b, &report, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_abs!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_abs!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_abs!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_abs!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_abs!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_abs!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_abs!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::__support::abs_appro_check(a, b) {
+                    $crate::__support::fail_ne(a, b, &report, true, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::abs_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+}
+
+/// Asserts that the absolute error of the two expressions is not small enough.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// Unlike assert_appro_ne_abs!, debug_assert_appro_ne_abs! statements are only enabled in non optimized builds by default.
+/// An optimized build will omit all debug_assert_appro_ne_abs! statements unless -C debug-assertions is passed to the compiler.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne_abs!(1f64, 1.5f64, 0.4f64); // does not panic
+/// debug_assert_appro_ne_abs!(1f64, 1.5f64, 0.4f64, msg = "seed: {}", 42); // does not panic
+/// debug_assert_appro_ne_abs!(0f64, 1e-10f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne_abs!(0f64, 1e-12f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! debug_assert_appro_ne_abs {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_ne_abs!($($arg)*); })
+}
+
+/// Asserts that the relative error of the two expressions is not small enough.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+/// The diff value can be followed by a `RelErrorDef` selecting the definition of the relative error.
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne_rel!(1f64, 1.5f64, 0.3f64); // does not panic
+/// assert_appro_ne_rel!(1f64, 1.5f64, 0.3f64, msg = "seed: {}", 42); // does not panic
+/// assert_appro_ne_rel!(1f64, 1.5f64, 0.3f64, "seed: {}", 42); // does not panic
+/// assert_appro_ne_rel!(1f64, 1.0 + 1e-10f64); // does not panic
+/// assert_appro_ne_rel!(1e-12f64, 0f64, 1.0, appro_eq::RelErrorDef::Expected); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// assert_appro_ne_rel!(1f64, 1.0 + 1e-12f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! assert_appro_ne_rel {
+    ($a:expr, $b:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Outliers::of($outliers, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Outliers::of($outliers, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, outliers = $outliers:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Outliers::of($outliers, &$a), $b, $eps, $def $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Norm::of($norm, &$a), $b $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Norm::of($norm, &$a), $b, $eps $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, norm = $norm:expr $(, $($rest:tt)+)?) => {{
+        $crate::assert_appro_ne_rel!($crate::Norm::of($norm, &$a), $b, $eps, $def $(, $($rest)+)?);
+    }};
+    ($a:expr, $b:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = {
+                    use $crate::__support::{RelCheckDiff as _, RelCheckOwn as _, RelCheckRatio as _};
+                    (&&&$crate::__support::RelCheck(a, b)).rel_appro_check()
+                } {
+                    $crate::__support::fail_ne(a, b, &report, true, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_rel!($a, $b, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b, $eps, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_rel!($a, $b, $eps, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b, $eps));
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr, msg = $($arg:tt)+) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b, $eps, $def, msg = $($arg)+));
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_rel!($a, $b, $eps, $def, policy = $policy, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, policy = $policy:expr) => {{
+        $crate::FloatPolicy::scope($policy, || $crate::assert_appro_ne_rel!($a, $b, $eps, $def));
+    }};
+    ($a:expr, $b:expr, $eps:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::rel_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_rel!($a, $b, $eps, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, msg = $($arg:tt)+) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::rel_appro_check_with_def(a, b, &$eps, $def) {
+                    $crate::__support::fail_ne(a, b, &report, false, Some(format_args!($($arg)+)));
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr, $fmt:literal $($args:tt)*) => {{
+        $crate::assert_appro_ne_rel!($a, $b, $eps, $def, msg = $fmt $($args)*);
+    }};
+    ($a:expr, $b:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = {
+                    use $crate::__support::{RelCheckDiff as _, RelCheckOwn as _, RelCheckRatio as _};
+                    (&&&$crate::__support::RelCheck(a, b)).rel_appro_check()
+                } {
+                    $crate::__support::fail_ne(a, b, &report, true, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::rel_appro_check(a, b, &$eps) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+    ($a:expr, $b:expr, $eps:expr, $def:expr) => {{
+        match (&$a, &$b) {
+            (a, b) => {
+                if let Ok(report) = $crate::rel_appro_check_with_def(a, b, &$eps, $def) {
+                    $crate::__support::fail_ne(a, b, &report, false, None);
+                }
+            }
+        }
+    }};
+}
+
+/// Asserts that the relative error of the two expressions is not small enough.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, Tolerance::tolerance() is the default used.
+/// The `outliers`, `norm`, `policy` and `msg` options can follow, as described in
+/// [Assertion options](crate#assertion-options).
+/// The diff value can be followed by a `RelErrorDef` selecting the definition of the relative error.
+///
+/// Values whose error cannot be measured, such as collections of different lengths or values
+/// with a NaN, are not approximately equal, so they pass the assertion.
+///
+/// Unlike assert_appro_ne_rel!, debug_assert_appro_ne_rel! statements are only enabled in non optimized builds by default.
+/// An optimized build will omit all debug_assert_appro_ne_rel! statements unless -C debug-assertions is passed to the compiler.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne_rel!(1f64, 1.5f64, 0.3f64); // does not panic
+/// debug_assert_appro_ne_rel!(1f64, 1.5f64, 0.3f64, msg = "seed: {}", 42); // does not panic
+/// debug_assert_appro_ne_rel!(1f64, 1.0 + 1e-10f64); // does not panic
+/// debug_assert_appro_ne_rel!(1e-12f64, 0f64, 1.0, appro_eq::RelErrorDef::Expected); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate appro_eq;
+/// # fn main() {
+/// debug_assert_appro_ne_rel!(1f64, 1.0 + 1e-12f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+macro_rules! debug_assert_appro_ne_rel {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_appro_ne_rel!($($arg)*); })
+}
@@ -3,25 +3,40 @@
 //! version 2.0 (the "License"). You can obtain a copy of the License at
 //! http://mozilla.org/MPL/2.0/.
 
+use crate::visit::Path;
 use crate::AbsError;
+use crate::ApproEqError;
 use crate::ApproEqResult;
 use crate::RelError;
+use crate::RelErrorDef;
+use crate::Segment;
+use crate::UlpsError;
 use num_complex::Complex;
-use num_traits::Float;
-use std::ops::{Div, Sub};
+use num_traits::{Float, NumCast};
+use std::ops::Sub;
+
+/// Locates an error of the real part.
+fn re(e: ApproEqError) -> ApproEqError {
+    Path::ROOT.join(&|| Segment::Field("re")).locate(e)
+}
+
+/// Locates an error of the imaginary part.
+fn im(e: ApproEqError) -> ApproEqError {
+    Path::ROOT.join(&|| Segment::Field("im")).locate(e)
+}
 
 #[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.1.0"))]
 impl<A, D: Float, B: AbsError<A, D>> AbsError<Complex<A>, D> for Complex<B> {
     fn abs_error(&self, expected: &Complex<A>) -> ApproEqResult<D> {
-        let diff_re = self.re.abs_error(&expected.re);
-        let diff_im = self.im.abs_error(&expected.im);
+        let diff_re = self.re.abs_error(&expected.re).map_err(re);
+        let diff_im = self.im.abs_error(&expected.im).map_err(im);
         if match (&diff_re, &diff_im) {
-            (&Err(_), _) => false,
-            (_, &Err(_)) => true,
-            (&Ok(ref diff_re), &Ok(ref diff_im)) => match (diff_re, diff_im) {
-                (&None, _) => true,
-                (_, &None) => false,
-                (&Some(ref diff_re), &Some(ref diff_im)) => {
+            (Err(_), _) => false,
+            (_, Err(_)) => true,
+            (Ok(diff_re), Ok(diff_im)) => match (diff_re, diff_im) {
+                (None, _) => true,
+                (_, None) => false,
+                (Some(diff_re), Some(diff_im)) => {
                     return Ok(Some(Float::sqrt(
                         (*diff_re * *diff_re) + (*diff_im * *diff_im),
                     )))
@@ -36,12 +51,39 @@ impl<A, D: Float, B: AbsError<A, D>> AbsError<Complex<A>, D> for Complex<B> {
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.1.0"))]
-impl<A: Float, D: Float + Div<A, Output = D>, B: Sub<A, Output = D> + Copy> RelError<Complex<A>, D>
-    for Complex<B>
-{
+impl<A: Float, D: Float, B: Float + Sub<A, Output = D>> RelError<Complex<A>, D> for Complex<B> {
     fn rel_error(&self, expected: &Complex<A>) -> ApproEqResult<D> {
-        Ok(Some(
-            Complex::new(self.re - expected.re, self.im - expected.im).norm() / expected.norm(),
-        ))
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Complex<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        let norm = |x: Option<D>| x.ok_or(ApproEqError::Overflow);
+        let base = def.base(
+            norm(<D as NumCast>::from(self.norm()))?,
+            norm(<D as NumCast>::from(expected.norm()))?,
+            |a, b| (a + b) / (D::one() + D::one()),
+        );
+        if base.is_zero() {
+            Err(ApproEqError::DividedByZero)
+        } else {
+            Ok(Some(
+                Complex::new(self.re - expected.re, self.im - expected.im).norm() / base,
+            ))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: UlpsError<A, D>> UlpsError<Complex<A>, D> for Complex<B> {
+    fn ulps_error(&self, expected: &Complex<A>) -> ApproEqResult<D> {
+        Ok(
+            match (
+                self.re.ulps_error(&expected.re).map_err(re)?,
+                self.im.ulps_error(&expected.im).map_err(im)?,
+            ) {
+                (Some(re), Some(im)) => Some(if im > re { im } else { re }),
+                (re, im) => re.or(im),
+            },
+        )
     }
 }
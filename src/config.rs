@@ -0,0 +1,232 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+//!
+//! Default tolerances loaded at process start, see the crate documentation.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const PREFIX: &str = "APPRO_EQ_";
+const CONFIG_VAR: &str = "APPRO_EQ_CONFIG";
+const FILE_NAME: &str = "appro-eq.toml";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    Tolerance,
+    Abs,
+    Rel,
+}
+
+impl Kind {
+    fn from_key(key: &str) -> Option<Kind> {
+        match key {
+            "tolerance" => Some(Kind::Tolerance),
+            "abs" => Some(Kind::Abs),
+            "rel" => Some(Kind::Rel),
+            _ => None,
+        }
+    }
+}
+
+type Value = Box<dyn Any + Send + Sync>;
+
+fn parse_value<T: std::str::FromStr + Send + Sync + 'static>(text: &str) -> Result<Value, String> {
+    text.parse::<T>()
+        .map(|v| Box::new(v) as Value)
+        .map_err(|_| format!("invalid value `{}`", text))
+}
+
+fn parse_duration(text: &str) -> Result<Value, String> {
+    match text.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs >= 0.0 => {
+            Ok(Box::new(Duration::from_secs_f64(secs)) as Value)
+        }
+        _ => Err(format!("invalid duration `{}`, expected seconds", text)),
+    }
+}
+
+macro_rules! parse_typed {
+    ($ty:expr, $text:expr, $($name:expr => $T:ty),+) => {
+        match $ty {
+            $($name => Some((TypeId::of::<$T>(), parse_value::<$T>($text))),)+
+            "duration" => Some((TypeId::of::<Duration>(), parse_duration($text))),
+            _ => None,
+        }
+    };
+}
+
+/// Parses `text` as a tolerance of the type named `ty`, or returns `None` if there is no such type.
+fn parse(ty: &str, text: &str) -> Option<Result<(TypeId, Value), String>> {
+    parse_typed!(ty, text,
+        "f32" => f32, "f64" => f64,
+        "i8" => i8, "i16" => i16, "i32" => i32, "i64" => i64, "i128" => i128, "isize" => isize,
+        "u8" => u8, "u16" => u16, "u32" => u32, "u64" => u64, "u128" => u128, "usize" => usize)
+    .map(|(id, value)| value.map(|value| (id, value)))
+}
+
+/// Removes a comment from `line`, which starts at a `#` outside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses a value, which is either bare or a basic string without escapes, rejecting any other TOML.
+fn unquote(value: &str) -> Result<&str, String> {
+    let inner = match value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        Some(inner) => inner,
+        None => value,
+    };
+    if inner.is_empty() || inner.contains(|c: char| "\"'\\".contains(c) || c.is_whitespace()) {
+        Err(format!("unsupported value `{}`", value))
+    } else {
+        Ok(inner)
+    }
+}
+
+/// The configured tolerances, and the errors of the entries that were ignored.
+#[derive(Default)]
+struct Config {
+    values: HashMap<(TypeId, Kind), Value>,
+    errors: Vec<String>,
+}
+
+impl Config {
+    fn set(&mut self, ty: &str, key: &str, text: &str) -> Result<(), String> {
+        let kind = Kind::from_key(key).ok_or_else(|| format!("unknown key `{}`", key))?;
+        let (id, value) = parse(ty, text).ok_or_else(|| format!("unknown type `{}`", ty))??;
+        self.values.insert((id, kind), value);
+        Ok(())
+    }
+
+    /// Reads the subset of TOML made of sections and `key = value` pairs, whose values are numbers
+    /// or strings without escapes. Other TOML is recorded as an error and ignored, line by line.
+    fn read_file(&mut self, path: &Path) {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.errors
+                    .push(format!("failed to read {}: {}", path.display(), e));
+                return;
+            }
+        };
+        let mut section = None;
+        for (n, line) in text.lines().enumerate() {
+            let error = |e: String| format!("failed to parse {}:{}: {}", path.display(), n + 1, e);
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = Some(line[1..line.len() - 1].trim().to_string());
+                continue;
+            }
+            let mut pair = line.splitn(2, '=');
+            let result = match (pair.next(), pair.next(), &section) {
+                (Some(key), Some(value), Some(ty)) => {
+                    unquote(value.trim()).and_then(|value| self.set(ty, key.trim(), value))
+                }
+                (Some(key), Some(_), None) => Err(format!("`{}` is not in a section", key.trim())),
+                _ => Err("expected `key = value`".to_string()),
+            };
+            if let Err(e) = result {
+                self.errors.push(error(e));
+            }
+        }
+    }
+
+    /// Reads the variables named after a type and a key, recording the other variables with the prefix
+    /// as errors.
+    fn read_env(&mut self) {
+        for (name, value) in env::vars_os() {
+            let name = match name.to_str() {
+                Some(name) if name != CONFIG_VAR && name.starts_with(PREFIX) => name,
+                _ => continue,
+            };
+            let name_lower = name[PREFIX.len()..].to_lowercase();
+            let (ty, kind) = match name_lower.rfind('_') {
+                Some(i) => match Kind::from_key(&name_lower[i + 1..]) {
+                    Some(kind) => (&name_lower[..i], kind),
+                    None => (name_lower.as_str(), Kind::Tolerance),
+                },
+                None => (name_lower.as_str(), Kind::Tolerance),
+            };
+            let parsed = value
+                .to_str()
+                .ok_or_else(|| "invalid unicode".to_string())
+                .and_then(|value| {
+                    parse(ty, value.trim()).ok_or_else(|| format!("unknown type `{}`", ty))?
+                });
+            match parsed {
+                Ok((id, value)) => {
+                    self.values.insert((id, kind), value);
+                }
+                Err(e) => self.errors.push(format!("failed to parse {}: {}", name, e)),
+            }
+        }
+    }
+
+    fn load() -> Config {
+        let mut config = Config::default();
+        let path = match env::var_os(CONFIG_VAR) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => env::var_os("CARGO_MANIFEST_DIR")
+                .map(|dir| Path::new(&dir).join(FILE_NAME))
+                .filter(|path| path.is_file()),
+        };
+        if let Some(path) = path {
+            config.read_file(&path);
+        }
+        config.read_env();
+        config
+    }
+
+    /// Returns the configured tolerance of `D`, loading the configuration on the first call
+    /// and reporting the entries ignored on the standard error.
+    fn get<D: Clone + 'static>(kind: Kind) -> Option<D> {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+        CONFIG
+            .get_or_init(|| {
+                let config = Config::load();
+                for e in &config.errors {
+                    eprintln!("appro-eq: {}, ignored", e);
+                }
+                config
+            })
+            .values
+            .get(&(TypeId::of::<D>(), kind))
+            .and_then(|value| value.downcast_ref::<D>())
+            .cloned()
+    }
+}
+
+/// Returns the configured `tolerance` of `D`.
+pub(crate) fn tolerance<D: Clone + 'static>() -> Option<D> {
+    Config::get(Kind::Tolerance)
+}
+
+/// Returns the configured absolute tolerance of `D`.
+pub(crate) fn abs<D: Clone + 'static>() -> Option<D> {
+    Config::get(Kind::Abs)
+}
+
+/// Returns the configured relative tolerance of `D`.
+pub(crate) fn rel<D: Clone + 'static>() -> Option<D> {
+    Config::get(Kind::Rel)
+}
@@ -0,0 +1,233 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::report::reduce;
+use crate::visit::{each, visit, Path, Visitor};
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::Segment;
+use crate::Tol;
+use crate::TolError;
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait TolsError<Rhs: ?Sized = Self, Tols: ?Sized = Self> {
+    /// This method tests for self(actual value) and expected values to be the ratio of the error of each element
+    /// to its own tolerance in `tols`, reduced to the largest one, which is 1 or less when approximately equal.
+    ///
+    /// `tols` has the shape of the values, and is either the absolute tolerances of the elements
+    /// or a `Weighted` tolerance. Tolerances of another shape fail with `ApproEqError::ShapeMismatch`,
+    /// except that the tolerances of `ndarray::ArrayBase` are broadcast to the shape of the values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn tols_error(&self, expected: &Rhs, tols: &Tols) -> ApproEqResult<f64>;
+
+    /// Passes `visitor` the ratios of the errors of the elements of self and `expected` to `tols` at `path`.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn tols_visit(
+        &self,
+        expected: &Rhs,
+        tols: &Tols,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.tols_error(expected, tols), path, visitor)
+    }
+}
+
+/// A tolerance scaled for each element of a collection by its weight.
+///
+/// The ratio of the error of an element to `tol`, as measured by `TolError`, is multiplied by its weight,
+/// so an element of weight 2 is held to half the tolerance and an element of weight 0 is not compared.
+/// `weights` has the shape of the values compared.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::{Tol, Weighted};
+/// # fn main() {
+/// let expected = vec![101_325f64, 293.15];
+/// let actual = vec![101_330f64, 293.16];
+/// assert_appro_eq!(actual, expected, tols = vec![10.0, 0.05]); // does not panic
+/// assert_appro_eq!(actual, expected, tols = Weighted::new(Tol::new().rel(1e-4), vec![1.0, 2.0])); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weighted<D, W> {
+    /// The tolerance of an element of weight 1.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub tol: Tol<D>,
+    /// The weights of the elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub weights: W,
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<D, W> Weighted<D, W> {
+    /// Scales `tol` by `weights`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn new(tol: Tol<D>, weights: W) -> Weighted<D, W> {
+        Weighted { tol, weights }
+    }
+}
+
+/// The ratio of the error of an element to its absolute tolerance `tol`.
+pub(crate) fn abs_ratio<A, B: TolError<A, D> + ?Sized, D: Clone>(
+    actual: &B,
+    expected: &A,
+    tol: &D,
+) -> ApproEqResult<f64> {
+    actual.tol_error(expected, &Tol::new().abs(tol.clone()))
+}
+
+/// The ratio of the error of an element to `tol`, scaled by its weight.
+pub(crate) fn weighted_ratio<A, B: TolError<A, D> + ?Sized, D>(
+    actual: &B,
+    expected: &A,
+    tol: &Tol<D>,
+    weight: f64,
+) -> ApproEqResult<f64> {
+    if weight == 0.0 {
+        return Ok(Some(0.0));
+    }
+    Ok(actual.tol_error(expected, tol)?.map(|ratio| ratio * weight))
+}
+
+/// Visits the ratios of the elements of two slices to the tolerances `tols`, all of the same length.
+fn elements<A, B, T, F>(
+    actual: &[B],
+    expected: &[A],
+    tols: &[T],
+    path: &Path<'_>,
+    visitor: &mut dyn Visitor<f64>,
+    ratio: F,
+) -> Result<(), ApproEqError>
+where
+    F: Fn(&B, &A, &T) -> ApproEqResult<f64>,
+{
+    if actual.len() != expected.len() {
+        Err(path.locate(ApproEqError::LengthMismatch))
+    } else if tols.len() != actual.len() {
+        Err(path.locate(ApproEqError::ShapeMismatch {
+            values: vec![actual.len()],
+            tols: vec![tols.len()],
+        }))
+    } else {
+        each(0..actual.len(), path, Segment::Index, |i, path| {
+            visit(ratio(&actual[i], &expected[i], &tols[i]), path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: Clone, B: TolError<A, D>> TolsError<[A], [D]> for [B] {
+    fn tols_error(&self, expected: &[A], tols: &[D]) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tols_visit(expected, tols, path, visitor))
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &[A],
+        tols: &[D],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, tols, path, visitor, abs_ratio)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>, W: AsRef<[f64]>> TolsError<[A], Weighted<D, W>> for [B] {
+    fn tols_error(&self, expected: &[A], tols: &Weighted<D, W>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tols_visit(expected, tols, path, visitor))
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &[A],
+        tols: &Weighted<D, W>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let weights = tols.weights.as_ref();
+        elements(self, expected, weights, path, visitor, |a, e, w| {
+            weighted_ratio(a, e, &tols.tol, *w)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: Clone, B: TolError<A, D>> TolsError<Vec<A>, Vec<D>> for Vec<B> {
+    fn tols_error(&self, expected: &Vec<A>, tols: &Vec<D>) -> ApproEqResult<f64> {
+        self.as_slice()
+            .tols_error(expected.as_slice(), tols.as_slice())
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &Vec<A>,
+        tols: &Vec<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .tols_visit(expected.as_slice(), tols.as_slice(), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>, W: AsRef<[f64]>> TolsError<Vec<A>, Weighted<D, W>> for Vec<B> {
+    fn tols_error(&self, expected: &Vec<A>, tols: &Weighted<D, W>) -> ApproEqResult<f64> {
+        self.as_slice().tols_error(expected.as_slice(), tols)
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &Vec<A>,
+        tols: &Weighted<D, W>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .tols_visit(expected.as_slice(), tols, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: Clone, B: TolError<A, D>, const N: usize> TolsError<[A; N], [D; N]> for [B; N] {
+    fn tols_error(&self, expected: &[A; N], tols: &[D; N]) -> ApproEqResult<f64> {
+        self[..].tols_error(&expected[..], &tols[..])
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &[A; N],
+        tols: &[D; N],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self[..].tols_visit(&expected[..], &tols[..], path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>, W: AsRef<[f64]>, const N: usize> TolsError<[A; N], Weighted<D, W>>
+    for [B; N]
+{
+    fn tols_error(&self, expected: &[A; N], tols: &Weighted<D, W>) -> ApproEqResult<f64> {
+        self[..].tols_error(&expected[..], tols)
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &[A; N],
+        tols: &Weighted<D, W>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self[..].tols_visit(&expected[..], tols, path, visitor)
+    }
+}
@@ -24,6 +24,91 @@
 //! assert_appro_eq!(1f64, 2f64); // panics
 //! # }
 //! ```
+//!
+//! # Assertion options
+//! The assertion macros take options after the two values and the diff value, separated by commas:
+//!
+//! - `tol = <Tol>` compares with a tolerance value instead of a diff value, in `assert_appro_eq!`
+//!   and `assert_appro_ne!`. `tols = <tolerances>` compares each element of a collection with its own tolerance,
+//!   given as a collection of the same shape or a `Weighted` tolerance.
+//! - `outliers = <Outliers>` allows some elements of collections to exceed the tolerance, as `Outliers::of`.
+//! - `norm = <Norm>` reduces the errors of the elements of collections by the norm, as `Norm::of`.
+//! - `policy = <FloatPolicy>` sets the policy for NaN, infinities and signed zeros. It follows `outliers`
+//!   and `norm`, which can come in either order.
+//! - `msg = <format string>, <args>...` comes last and adds a custom message to the failure message.
+//!   After the diff value or the policy, the format string can also follow directly, as in `assert_eq!`.
+//!
+//! ```rust
+//! # #[macro_use] extern crate appro_eq;
+//! # use appro_eq::{FloatPolicy, Norm, Outliers};
+//! # fn main() {
+//! let actual = vec![1f64, 2.0, 3.5];
+//! let expected = vec![1f64, 2.0, 3.0];
+//! assert_appro_eq!(actual, expected, 1e-6, outliers = Outliers::count(1)); // does not panic
+//! assert_appro_eq!(actual, expected, 0.3, norm = Norm::Rms, msg = "seed: {}", 42); // does not panic
+//! assert_appro_eq!(f64::NAN, f64::NAN, policy = FloatPolicy { nan_eq: true, ..FloatPolicy::default() }); // does not panic
+//! # }
+//! ```
+//!
+//! # Configuration
+//! The default tolerances of `f32`, `f64`, the integer types and `Duration` can be overridden at process start
+//! from `appro-eq.toml` and from environment variables, which take precedence over the file.
+//! The file is the one named by `APPRO_EQ_CONFIG`, or else `appro-eq.toml` next to the manifest
+//! of the crate being tested(`CARGO_MANIFEST_DIR`), if it exists.
+//!
+//! ```toml
+//! [f64]
+//! abs = 1e-9
+//! rel = 1e-6
+//!
+//! [duration]
+//! abs = 0.5 # seconds
+//! ```
+//!
+//! A section is named after the type, `duration` for `Duration`. Its keys are `abs` and `rel`, and `tolerance`,
+//! which is returned by `Tolerance::tolerance()` and so is the fallback of the other two.
+//! The environment variables are named `APPRO_EQ_<TYPE>_ABS`, `APPRO_EQ_<TYPE>_REL` and `APPRO_EQ_<TYPE>`,
+//! such as `APPRO_EQ_F64_ABS=1e-9`. `Duration` is given in seconds.
+//! The file may only hold numbers and strings without escapes as values.
+//! Invalid entries of the file, and variables with the prefix that are invalid or name no type and key,
+//! are ignored and reported on the standard error when the configuration is first used.
+//!
+//! # Derive
+//! With the `derive` feature, `#[derive(AbsError, RelError)]` implements the traits for structs and enums
+//! by comparing each field with its own impl and reducing the errors of the fields as for slices.
+//! The path of a failure names the field, such as `.samples[2]`.
+//! Enum values of different variants are compared as `ApproEqError::VariantMismatch`.
+//! Fields take the following attributes:
+//!
+//! - `#[appro_eq(skip)]` ignores the field.
+//! - `#[appro_eq(exact)]` requires the field to be equal with `PartialEq`, or else `ApproEqError::ValueMismatch`.
+//! - `#[appro_eq(abs = 0.5)]` and `#[appro_eq(rel = 0.1)]` check the field against its own tolerance
+//!   for `AbsError` and `RelError` respectively, instead of contributing to the error of the whole.
+//!   A field beyond it fails the comparison with `ApproEqError::ComponentError`.
+//!   The type of the field may not have type parameters.
+//!
+//! ```rust
+//! # #[macro_use] extern crate appro_eq;
+//! # #[cfg(feature = "derive")]
+//! # fn main() {
+//! use appro_eq::{AbsError, RelError};
+//!
+//! #[derive(Debug, AbsError, RelError)]
+//! struct Sample {
+//!     value: f64,
+//!     #[appro_eq(exact)]
+//!     id: u32,
+//!     #[appro_eq(abs = 0.5)]
+//!     coarse: f64,
+//! }
+//!
+//! let a = Sample { value: 1.0, id: 7, coarse: 1.0 };
+//! let b = Sample { value: 1.0 + 1e-12, id: 7, coarse: 1.25 };
+//! assert_appro_eq_abs!(a, b);
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
 
 #![cfg_attr(feature = "docs", feature(staged_api))]
 #![cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -42,16 +127,63 @@ mod rational_impl;
 #[cfg(feature = "ndarray")]
 mod ndarray_impl;
 
+mod elementwise;
+mod map_impl;
+#[macro_use]
+mod norm;
+mod outliers;
+mod time_impl;
+
+mod config;
+mod policy;
+mod report;
+mod tol;
+mod visit;
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::elementwise::{TolsError, Weighted};
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::norm::{Magnitude, Norm, Normed};
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::outliers::{Outliers, WithOutliers};
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::policy::FloatPolicy;
+#[doc(hidden)]
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+pub use crate::report::support as __support;
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::report::{
+    abs_appro_check, mixed_appro_check, rel_appro_check, rel_appro_check_with_def, tol_appro_check,
+    tols_appro_check, ulps_appro_check, Mismatch, Report, Segment,
+};
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::time_impl::Elapsed;
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub use crate::tol::{with_tolerance, Tol};
+
+#[cfg(feature = "appro-eq-derive")]
+#[cfg_attr(feature = "docs", stable(feature = "derive", since = "0.1.0"))]
+pub use appro_eq_derive::{AbsError, RelError};
+
+use crate::report::reduce;
+use crate::tol::Query;
+use crate::visit::{each, visit, Path, Visitor};
+use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::ops::{Add, Deref, Div, Rem};
+use std::pin::Pin;
 use std::rc::{Rc, Weak};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, TryLockError};
 use std::time::{Duration, Instant, SystemTime};
 
 use std::error;
 use std::fmt;
 
+/// The error measured by a comparison, `None` if there was nothing to measure.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-type ApproEqResult<D> = Result<Option<D>, ApproEqError>;
+pub type ApproEqResult<D> = Result<Option<D>, ApproEqError>;
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 #[derive(Debug)]
@@ -65,12 +197,117 @@ pub enum ApproEqError {
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     Overflow,
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    SignMismatch,
+    /// The values are different variants of an enum.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    VariantMismatch,
+    /// The values of a field compared exactly are not equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    ValueMismatch,
+    /// The maps do not have the same keys, given in `Debug` format.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    KeyMismatch {
+        /// The keys of the expected map that the actual map does not have.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        missing: Vec<String>,
+        /// The keys of the actual map that the expected map does not have.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        extra: Vec<String>,
+    },
+    /// The shape of the expected `ndarray::ArrayBase` cannot be broadcast to the shape of the actual one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    BroadcastMismatch {
+        /// The shape of the actual array.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        actual: Vec<usize>,
+        /// The shape of the expected array.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        expected: Vec<usize>,
+    },
+    /// The tolerances of the elements do not have, or for `ndarray::ArrayBase` broadcast to,
+    /// the shape of the values compared.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    ShapeMismatch {
+        /// The shape of the values, their length for sequences.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        values: Vec<usize>,
+        /// The shape of the tolerances.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        tols: Vec<usize>,
+    },
+    /// A `Mutex` or `RwLock` compared is poisoned.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Poisoned,
+    /// A `Mutex` or `RwLock` compared cannot be locked as the comparison already holds it,
+    /// such as a `Mutex` compared with itself.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Locked,
+    /// More elements exceed the tolerance than the outliers allowed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    TooManyOutliers {
+        /// The number of elements allowed to exceed the tolerance.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        allowed: usize,
+        /// The paths to the elements exceeding the tolerance.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        outliers: Vec<Vec<Segment>>,
+    },
+    /// An element exceeds the given cap of the outliers allowed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    OutlierCap(#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))] f64),
+    /// The time measured by an `Elapsed` is earlier than its origin, by the given `Duration`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    BeforeOrigin(
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))] Duration,
+    ),
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     ComponentError(
         #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
         Box<dyn error::Error>,
     ),
+    /// An error of an element of a collection, at the path from the collection to it.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    At {
+        /// The path from the outermost collection to the element, never empty.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        path: Vec<Segment>,
+        /// The error of the element, never itself `ApproEqError::At`.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        cause: Box<ApproEqError>,
+    },
+}
+
+impl ApproEqError {
+    /// The path to the element the error occurred at, empty if it occurred at the compared values themselves.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn path(&self) -> &[Segment] {
+        match self {
+            ApproEqError::At { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// The error without the path to the element it occurred at.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn inner(&self) -> &ApproEqError {
+        match self {
+            ApproEqError::At { cause, .. } => cause,
+            _ => self,
+        }
+    }
+
+    /// Splits the error into the path to the element it occurred at and the error without it.
+    pub(crate) fn split(self) -> (Vec<Segment>, ApproEqError) {
+        match self {
+            ApproEqError::At { path, cause } => (path, *cause),
+            cause => (Vec::new(), cause),
+        }
+    }
 }
 
+/// The number of outliers listed by `ApproEqError::TooManyOutliers`.
+const OUTLIER_LIMIT: usize = 10;
+
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl fmt::Display for ApproEqError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -79,15 +316,135 @@ impl fmt::Display for ApproEqError {
             ApproEqError::NonNumDifference => write!(f, "non num difference"),
             ApproEqError::DividedByZero => write!(f, "divided by zero"),
             ApproEqError::Overflow => write!(f, "overflow"),
+            ApproEqError::SignMismatch => write!(f, "sign mismatch"),
+            ApproEqError::VariantMismatch => write!(f, "variant mismatch"),
+            ApproEqError::ValueMismatch => write!(f, "value mismatch"),
+            ApproEqError::Poisoned => write!(f, "poisoned lock"),
+            ApproEqError::Locked => write!(f, "lock already held by the comparison"),
+            ApproEqError::TooManyOutliers { allowed, outliers } => {
+                write!(
+                    f,
+                    "too many outliers ({} elements exceed the tolerance, {} allowed: ",
+                    outliers.len(),
+                    allowed
+                )?;
+                for (n, path) in outliers.iter().take(OUTLIER_LIMIT).enumerate() {
+                    if n > 0 {
+                        write!(f, ", ")?;
+                    }
+                    for segment in path {
+                        write!(f, "{}", segment)?;
+                    }
+                }
+                if outliers.len() > OUTLIER_LIMIT {
+                    write!(f, " and {} more", outliers.len() - OUTLIER_LIMIT)?;
+                }
+                write!(f, ")")
+            }
+            ApproEqError::OutlierCap(cap) => write!(f, "element exceeds outlier cap `{:?}`", cap),
+            ApproEqError::BeforeOrigin(by) => write!(f, "time {:?} before origin", by),
+            ApproEqError::BroadcastMismatch { actual, expected } => write!(
+                f,
+                "shape mismatch (expected shape {:?} cannot be broadcast to actual shape {:?})",
+                expected, actual
+            ),
+            ApproEqError::ShapeMismatch { values, tols } => write!(
+                f,
+                "shape mismatch (tolerances of shape {:?} for values of shape {:?})",
+                tols, values
+            ),
+            ApproEqError::KeyMismatch { missing, extra } => {
+                write!(f, "key mismatch")?;
+                let mut separator = " (";
+                for (name, keys) in [("missing", missing), ("extra", extra)] {
+                    if !keys.is_empty() {
+                        write!(f, "{}{}: {}", separator, name, keys.join(", "))?;
+                        separator = "; ";
+                    }
+                }
+                if separator != " (" {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
             ApproEqError::ComponentError(ref err) => write!(f, "{}", err),
+            ApproEqError::At { path, cause } => {
+                write!(f, "{} at ", cause)?;
+                for segment in path {
+                    write!(f, "{}", segment)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl error::Error for ApproEqError {
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ApproEqError::ComponentError(err) => Some(err.as_ref()),
+            ApproEqError::At { cause, .. } => Some(cause.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Definition of the relative error, selecting the magnitude the absolute error is divided by.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RelErrorDef {
+    /// `|actual - expected| / |expected|`
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Expected,
+    /// `|actual - expected| / max(|actual|, |expected|)`
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Max,
+    /// `|actual - expected| / ((|actual| + |expected|) / 2)`
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Mean,
+    /// `|actual - expected| / min(|actual|, |expected|)`
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Min,
+}
+
+/// The default divides by the magnitude of the expected value, the definition `rel_error` has always used,
+/// so that existing relative tolerances keep their meaning and a zero expected value still fails with
+/// `ApproEqError::DividedByZero`. Taking the magnitude makes it safe for negative values;
+/// `Max` is the symmetric definition, never dividing by less than the magnitude of either value.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Default for RelErrorDef {
+    fn default() -> RelErrorDef {
+        RelErrorDef::Expected
+    }
+}
+
+impl RelErrorDef {
+    /// Selects the divisor from the magnitudes of the actual and expected values.
+    pub(crate) fn base<T: PartialOrd>(
+        self,
+        actual: T,
+        expected: T,
+        mean: impl FnOnce(T, T) -> T,
+    ) -> T {
+        match self {
+            RelErrorDef::Expected => expected,
+            RelErrorDef::Max => {
+                if actual > expected {
+                    actual
+                } else {
+                    expected
+                }
+            }
+            RelErrorDef::Mean => mean(actual, expected),
+            RelErrorDef::Min => {
+                if actual < expected {
+                    actual
+                } else {
+                    expected
+                }
+            }
+        }
     }
 }
 
@@ -96,6 +453,25 @@ pub trait RelError<Rhs: ?Sized = Self, Diff = Self> {
     /// This method tests for self(actual value) and expected values to be relative error.
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn rel_error(&self, expected: &Rhs) -> ApproEqResult<Diff>;
+
+    /// This method tests for self(actual value) and expected values to be relative error,
+    /// dividing the absolute error by the magnitude selected by `def`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn rel_error_with_def(&self, expected: &Rhs, def: RelErrorDef) -> ApproEqResult<Diff>;
+
+    /// Passes `visitor` the relative errors, as defined by `def`, of the elements of self and `expected`
+    /// at `path`, which collections visit one by one and other values as a whole.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn rel_visit(
+        &self,
+        expected: &Rhs,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<Diff>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.rel_error_with_def(expected, def), path, visitor)
+    }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -103,6 +479,84 @@ pub trait AbsError<Rhs: ?Sized = Self, Diff = Self> {
     /// This method tests for self(actual value) and expected values to be absolute error.
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn abs_error(&self, expected: &Rhs) -> ApproEqResult<Diff>;
+
+    /// Passes `visitor` the absolute errors of the elements of self and `expected` at `path`,
+    /// which collections visit one by one and other values as a whole.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn abs_visit(
+        &self,
+        expected: &Rhs,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<Diff>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.abs_error(expected), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait UlpsError<Rhs: ?Sized = Self, Diff = u64> {
+    /// This method tests for self(actual value) and expected values to be the distance in units in the last place.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_error(&self, expected: &Rhs) -> ApproEqResult<Diff>;
+
+    /// Passes `visitor` the distances in units in the last place of the elements of self and `expected`
+    /// at `path`, which collections visit one by one and other values as a whole.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn ulps_visit(
+        &self,
+        expected: &Rhs,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<Diff>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.ulps_error(expected), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait MixedError<Rhs: ?Sized = Self, Diff = Self> {
+    /// This method tests for self(actual value) and expected values to be the ratio of the absolute error
+    /// to `max(abs_tol, rel_tol * max(|actual|, |expected|))`, which is 1 or less when approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn mixed_error(&self, expected: &Rhs, abs_tol: &Diff, rel_tol: &Diff) -> ApproEqResult<f64>;
+
+    /// Passes `visitor` the ratios measured by `mixed_error` of the elements of self and `expected`
+    /// at `path`, which collections visit one by one and other values as a whole.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn mixed_visit(
+        &self,
+        expected: &Rhs,
+        abs_tol: &Diff,
+        rel_tol: &Diff,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.mixed_error(expected, abs_tol, rel_tol), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait TolError<Rhs: ?Sized = Self, Diff = Self> {
+    /// This method tests for self(actual value) and expected values to be the ratio of the error to `tol`,
+    /// for the most lenient of the criteria set in `tol`, which is 1 or less when approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn tol_error(&self, expected: &Rhs, tol: &Tol<Diff>) -> ApproEqResult<f64>;
+
+    /// Passes `visitor` the ratios measured by `tol_error` of the elements of self and `expected`
+    /// at `path`, which collections visit one by one and other values as a whole.
+    #[doc(hidden)]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn tol_visit(
+        &self,
+        expected: &Rhs,
+        tol: &Tol<Diff>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        visit(self.tol_error(expected, tol), path, visitor)
+    }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -115,25 +569,48 @@ pub trait AbsTolerance<Diff = Self> {
 pub trait RelTolerance<Diff = Self> {
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn rel_tolerance() -> Diff;
+
+    /// The definition of the relative error that `rel_tolerance` is meant for.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn rel_error_def() -> RelErrorDef {
+        RelErrorDef::default()
+    }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 pub trait Tolerance<Diff = Self> {
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn tolerance() -> Diff;
+
+    /// The tolerance asked for by `query`, which the blanket implementations of `AbsTolerance` and
+    /// `RelTolerance` return.
+    #[doc(hidden)]
+    #[inline]
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn queried_tolerance(query: Query) -> Diff {
+        let _ = query;
+        Self::tolerance()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait UlpsTolerance<Diff = Self> {
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_tolerance() -> Diff;
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl<Diff: Tolerance<Diff>> AbsTolerance<Diff> for Diff {
     fn abs_tolerance() -> Diff {
-        Diff::tolerance()
+        Diff::queried_tolerance(Query::Abs)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl<Diff: Tolerance<Diff>> RelTolerance<Diff> for Diff {
     fn rel_tolerance() -> Diff {
-        Diff::tolerance()
+        Diff::queried_tolerance(Query::Rel)
     }
 }
 
@@ -198,6 +675,16 @@ pub trait RelApproEqWithTol<Rhs: ?Sized = Self, Diff = Self> {
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn rel_appro_eq_with_tol(&self, other: &Rhs, tol: &Diff) -> bool;
 
+    /// This method tests for approximately equal, using the relative error definition `def`.
+    ///
+    /// The default implementation supports the default definition only, by `rel_appro_eq_with_tol`,
+    /// and is false for others.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn rel_appro_eq_with_tol_def(&self, other: &Rhs, tol: &Diff, def: RelErrorDef) -> bool {
+        def == RelErrorDef::default() && self.rel_appro_eq_with_tol(other, tol)
+    }
+
     /// This method tests for not approximately equal.
     #[inline]
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -210,7 +697,12 @@ pub trait RelApproEqWithTol<Rhs: ?Sized = Self, Diff = Self> {
 impl<Rhs, Diff: PartialOrd, T: RelError<Rhs, Diff>> RelApproEqWithTol<Rhs, Diff> for T {
     #[inline]
     fn rel_appro_eq_with_tol(&self, other: &Rhs, tol: &Diff) -> bool {
-        match self.rel_error(other) {
+        self.rel_appro_eq_with_tol_def(other, tol, RelErrorDef::default())
+    }
+
+    #[inline]
+    fn rel_appro_eq_with_tol_def(&self, other: &Rhs, tol: &Diff, def: RelErrorDef) -> bool {
+        match self.rel_error_with_def(other, def) {
             Ok(ref val) => match val {
                 Some(ref val) => val <= tol,
                 None => true,
@@ -227,6 +719,16 @@ pub trait RelApproEq<Rhs: ?Sized = Self, Diff = Self> {
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
     fn rel_appro_eq(&self, other: &Rhs) -> bool;
 
+    /// This method tests for approximately equal, using the relative error definition `def`.
+    ///
+    /// The default implementation supports the default definition only, by `rel_appro_eq`,
+    /// and is false for others.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn rel_appro_eq_with_def(&self, other: &Rhs, def: RelErrorDef) -> bool {
+        def == RelErrorDef::default() && self.rel_appro_eq(other)
+    }
+
     /// This method tests for not approximately equal.
     #[inline]
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -241,365 +743,2200 @@ impl<Rhs, Diff: PartialOrd + RelTolerance<Diff>, T: RelApproEqWithTol<Rhs, Diff>
 {
     #[inline]
     fn rel_appro_eq(&self, other: &Rhs) -> bool {
-        self.rel_appro_eq_with_tol(other, &Diff::rel_tolerance())
+        self.rel_appro_eq_with_tol_def(other, &Diff::rel_tolerance(), Diff::rel_error_def())
+    }
+
+    #[inline]
+    fn rel_appro_eq_with_def(&self, other: &Rhs, def: RelErrorDef) -> bool {
+        self.rel_appro_eq_with_tol_def(other, &Diff::rel_tolerance(), def)
     }
 }
 
-/// tolerance is 1e-6 for f32
+/// Trait for approximately equality comparisons.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl Tolerance for f32 {
-    fn tolerance() -> f32 {
-        1e-6
+pub trait UlpsApproEqWithTol<Rhs: ?Sized = Self, Diff = u64> {
+    /// This method tests for approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_appro_eq_with_tol(&self, other: &Rhs, tol: &Diff) -> bool;
+
+    /// This method tests for not approximately equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_appro_ne_with_tol(&self, other: &Rhs, tol: &Diff) -> bool {
+        !self.ulps_appro_eq_with_tol(other, tol)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl AbsError for f32 {
-    fn abs_error(&self, expected: &f32) -> ApproEqResult<f32> {
-        Ok(Some((self - expected).abs()))
+impl<Rhs, Diff: PartialOrd, T: UlpsError<Rhs, Diff>> UlpsApproEqWithTol<Rhs, Diff> for T {
+    #[inline]
+    fn ulps_appro_eq_with_tol(&self, other: &Rhs, tol: &Diff) -> bool {
+        match self.ulps_error(other) {
+            Ok(ref val) => match val {
+                Some(ref val) => val <= tol,
+                None => true,
+            },
+            Err(_) => false,
+        }
     }
 }
 
+/// Trait for approximately equality comparisons.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl RelError for f32 {
-    fn rel_error(&self, expected: &f32) -> ApproEqResult<f32> {
-        if *expected == 0.0 {
-            Err(ApproEqError::DividedByZero)
-        } else {
-            Ok(Some((self - expected).abs() / expected))
-        }
+pub trait UlpsApproEq<Rhs: ?Sized = Self, Diff = u64> {
+    /// This method tests for approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_appro_eq(&self, other: &Rhs) -> bool;
+
+    /// This method tests for not approximately equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn ulps_appro_ne(&self, other: &Rhs) -> bool {
+        !self.ulps_appro_eq(other)
     }
 }
 
-/// tolerance is 1e-6 for f64
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl Tolerance for f64 {
-    fn tolerance() -> f64 {
-        1e-11
+impl<Rhs, Diff: PartialOrd + UlpsTolerance<Diff>, T: UlpsApproEqWithTol<Rhs, Diff>>
+    UlpsApproEq<Rhs, Diff> for T
+{
+    #[inline]
+    fn ulps_appro_eq(&self, other: &Rhs) -> bool {
+        self.ulps_appro_eq_with_tol(other, &Diff::ulps_tolerance())
     }
 }
 
+/// Trait for approximately equality comparisons.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl AbsError for f64 {
-    fn abs_error(&self, expected: &f64) -> ApproEqResult<f64> {
-        Ok(Some((self - expected).abs()))
+pub trait MixedApproEqWithTol<Rhs: ?Sized = Self, Diff = Self> {
+    /// This method tests for approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn mixed_appro_eq_with_tol(&self, other: &Rhs, abs_tol: &Diff, rel_tol: &Diff) -> bool;
+
+    /// This method tests for not approximately equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn mixed_appro_ne_with_tol(&self, other: &Rhs, abs_tol: &Diff, rel_tol: &Diff) -> bool {
+        !self.mixed_appro_eq_with_tol(other, abs_tol, rel_tol)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl RelError for f64 {
-    fn rel_error(&self, expected: &f64) -> ApproEqResult<f64> {
-        if *expected == 0.0 {
-            Err(ApproEqError::DividedByZero)
-        } else {
-            Ok(Some((self - expected).abs() / expected))
+impl<Rhs, Diff, T: MixedError<Rhs, Diff>> MixedApproEqWithTol<Rhs, Diff> for T {
+    #[inline]
+    fn mixed_appro_eq_with_tol(&self, other: &Rhs, abs_tol: &Diff, rel_tol: &Diff) -> bool {
+        match self.mixed_error(other, abs_tol, rel_tol) {
+            Ok(ref val) => match val {
+                Some(ref val) => *val <= 1.0,
+                None => true,
+            },
+            Err(_) => false,
         }
     }
 }
 
+/// Trait for approximately equality comparisons.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl AbsError<f64, f32> for f32 {
-    fn abs_error(&self, expected: &f64) -> ApproEqResult<f32> {
-        Ok(Some((f64::from(*self) - expected).abs() as f32))
+pub trait MixedApproEq<Rhs: ?Sized = Self, Diff = Self> {
+    /// This method tests for approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn mixed_appro_eq(&self, other: &Rhs) -> bool;
+
+    /// This method tests for not approximately equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn mixed_appro_ne(&self, other: &Rhs) -> bool {
+        !self.mixed_appro_eq(other)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl RelError<f64, f32> for f32 {
-    fn rel_error(&self, expected: &f64) -> ApproEqResult<f32> {
-        if *expected == 0.0 {
-            Err(ApproEqError::DividedByZero)
-        } else {
-            Ok(Some(
-                ((f64::from(*self) - expected).abs() / expected) as f32,
-            ))
+impl<Rhs, Diff: AbsTolerance<Diff> + RelTolerance<Diff>, T: MixedApproEqWithTol<Rhs, Diff>>
+    MixedApproEq<Rhs, Diff> for T
+{
+    #[inline]
+    fn mixed_appro_eq(&self, other: &Rhs) -> bool {
+        self.mixed_appro_eq_with_tol(other, &Diff::abs_tolerance(), &Diff::rel_tolerance())
+    }
+}
+
+/// Trait for approximately equality comparisons.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait TolApproEq<Rhs: ?Sized = Self, Diff = Self> {
+    /// This method tests for approximately equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn tol_appro_eq(&self, other: &Rhs, tol: &Tol<Diff>) -> bool;
+
+    /// This method tests for not approximately equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn tol_appro_ne(&self, other: &Rhs, tol: &Tol<Diff>) -> bool {
+        !self.tol_appro_eq(other, tol)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<Rhs, Diff, T: TolError<Rhs, Diff>> TolApproEq<Rhs, Diff> for T {
+    #[inline]
+    fn tol_appro_eq(&self, other: &Rhs, tol: &Tol<Diff>) -> bool {
+        let eq = || match self.tol_error(other, tol) {
+            Ok(ref val) => match val {
+                Some(ref val) => *val <= 1.0,
+                None => true,
+            },
+            Err(_) => false,
+        };
+        match tol.policy {
+            Some(policy) => policy.scope(eq),
+            None => eq(),
         }
     }
 }
 
+/// ulps tolerance is 4 for u32
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl AbsError<f32, f32> for f64 {
-    fn abs_error(&self, expected: &f32) -> ApproEqResult<f32> {
-        Ok(Some((self - f64::from(*expected)).abs() as f32))
+impl UlpsTolerance for u32 {
+    fn ulps_tolerance() -> u32 {
+        tol::ulps_tolerance::<f32>().map_or(4, |ulps| u32::try_from(ulps).unwrap_or(u32::MAX))
     }
 }
 
+/// ulps tolerance is 4 for u64
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl RelError<f32, f32> for f64 {
-    fn rel_error(&self, expected: &f32) -> ApproEqResult<f32> {
-        if *expected == 0.0 {
+impl UlpsTolerance for u64 {
+    fn ulps_tolerance() -> u64 {
+        tol::ulps_tolerance::<f64>().unwrap_or(4)
+    }
+}
+
+fn rel_ratio(actual: f64, expected: f64, def: RelErrorDef) -> ApproEqResult<f64> {
+    FloatPolicy::measure(actual, expected, 0.0, || {
+        let base = def.base(actual.abs(), expected.abs(), |a, b| (a + b) / 2.0);
+        let ratio = (actual - expected).abs() / base;
+        if base == 0.0 {
             Err(ApproEqError::DividedByZero)
+        } else if ratio.is_nan() {
+            Err(ApproEqError::NonNumDifference)
         } else {
-            Ok(Some(
-                ((self - f64::from(*expected)).abs() / f64::from(*expected)) as f32,
-            ))
+            Ok(Some(ratio))
         }
-    }
+    })
 }
 
-macro_rules! itype_impls {
-    ($($T:ty)+) => {
-        $(
-            /// tolerance is zero for $T
+/// tolerance is 1e-6 for f32
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Tolerance for f32 {
+    fn tolerance() -> f32 {
+        Self::queried_tolerance(Query::Tolerance)
+    }
+
+    fn queried_tolerance(query: Query) -> f32 {
+        tol::default_tolerance(query, 1e-6)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError for f32 {
+    fn abs_error(&self, expected: &f32) -> ApproEqResult<f32> {
+        FloatPolicy::measure(f64::from(*self), f64::from(*expected), 0.0, || {
+            Ok(Some((self - expected).abs()))
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelError for f32 {
+    fn rel_error(&self, expected: &f32) -> ApproEqResult<f32> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &f32, def: RelErrorDef) -> ApproEqResult<f32> {
+        rel_ratio(f64::from(*self), f64::from(*expected), def).map(|v| v.map(|v| v as f32))
+    }
+}
+
+/// tolerance is 1e-6 for f64
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Tolerance for f64 {
+    fn tolerance() -> f64 {
+        Self::queried_tolerance(Query::Tolerance)
+    }
+
+    fn queried_tolerance(query: Query) -> f64 {
+        tol::default_tolerance(query, 1e-11)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError for f64 {
+    fn abs_error(&self, expected: &f64) -> ApproEqResult<f64> {
+        FloatPolicy::measure(*self, *expected, 0.0, || Ok(Some((self - expected).abs())))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelError for f64 {
+    fn rel_error(&self, expected: &f64) -> ApproEqResult<f64> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &f64, def: RelErrorDef) -> ApproEqResult<f64> {
+        rel_ratio(*self, *expected, def)
+    }
+}
+
+fn mixed_ratio(actual: f64, expected: f64, abs_tol: f64, rel_tol: f64) -> ApproEqResult<f64> {
+    FloatPolicy::measure(actual, expected, 0.0, || {
+        Tol::new()
+            .abs(abs_tol)
+            .rel(rel_tol)
+            .rel_def(RelErrorDef::Max)
+            .ratio(
+                (actual - expected).abs(),
+                actual.abs(),
+                expected.abs(),
+                || Ok(None),
+            )
+    })
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl MixedError for f32 {
+    fn mixed_error(&self, expected: &f32, abs_tol: &f32, rel_tol: &f32) -> ApproEqResult<f64> {
+        mixed_ratio(
+            f64::from(*self),
+            f64::from(*expected),
+            f64::from(*abs_tol),
+            f64::from(*rel_tol),
+        )
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl MixedError for f64 {
+    fn mixed_error(&self, expected: &f64, abs_tol: &f64, rel_tol: &f64) -> ApproEqResult<f64> {
+        mixed_ratio(*self, *expected, *abs_tol, *rel_tol)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl MixedError<f64, f32> for f32 {
+    fn mixed_error(&self, expected: &f64, abs_tol: &f32, rel_tol: &f32) -> ApproEqResult<f64> {
+        mixed_ratio(
+            f64::from(*self),
+            *expected,
+            f64::from(*abs_tol),
+            f64::from(*rel_tol),
+        )
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl MixedError<f32, f32> for f64 {
+    fn mixed_error(&self, expected: &f32, abs_tol: &f32, rel_tol: &f32) -> ApproEqResult<f64> {
+        mixed_ratio(
+            *self,
+            f64::from(*expected),
+            f64::from(*abs_tol),
+            f64::from(*rel_tol),
+        )
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl TolError for f32 {
+    fn tol_error(&self, expected: &f32, tol: &Tol<f32>) -> ApproEqResult<f64> {
+        let tol = tol.to_f64(|v| f64::from(*v), f32::abs_tolerance);
+        FloatPolicy::measure(f64::from(*self), f64::from(*expected), 0.0, || {
+            tol.ratio(
+                f64::from((self - expected).abs()),
+                f64::from(self.abs()),
+                f64::from(expected.abs()),
+                || self.ulps_error(expected).map(|v| v.map(u64::from)),
+            )
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl TolError for f64 {
+    fn tol_error(&self, expected: &f64, tol: &Tol<f64>) -> ApproEqResult<f64> {
+        let tol = tol.to_f64(|v| *v, f64::abs_tolerance);
+        FloatPolicy::measure(*self, *expected, 0.0, || {
+            tol.ratio((self - expected).abs(), self.abs(), expected.abs(), || {
+                self.ulps_error(expected)
+            })
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl UlpsError<f32, u32> for f32 {
+    fn ulps_error(&self, expected: &f32) -> ApproEqResult<u32> {
+        // Reorder the bit patterns so that adjacent floats map to adjacent integers,
+        // with -0.0 and +0.0 both mapped to zero.
+        let ordered = |x: f32| {
+            let bits = x.to_bits() as i32;
+            if bits < 0 {
+                i32::MIN - bits
+            } else {
+                bits
+            }
+        };
+        FloatPolicy::measure(f64::from(*self), f64::from(*expected), 0, || {
+            Ok(Some(ordered(*self).abs_diff(ordered(*expected))))
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl UlpsError for f64 {
+    fn ulps_error(&self, expected: &f64) -> ApproEqResult<u64> {
+        // Reorder the bit patterns so that adjacent floats map to adjacent integers,
+        // with -0.0 and +0.0 both mapped to zero.
+        let ordered = |x: f64| {
+            let bits = x.to_bits() as i64;
+            if bits < 0 {
+                i64::MIN - bits
+            } else {
+                bits
+            }
+        };
+        FloatPolicy::measure(*self, *expected, 0, || {
+            Ok(Some(ordered(*self).abs_diff(ordered(*expected))))
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError<f64, f32> for f32 {
+    fn abs_error(&self, expected: &f64) -> ApproEqResult<f32> {
+        FloatPolicy::measure(f64::from(*self), *expected, 0.0, || {
+            Ok(Some((f64::from(*self) - expected).abs() as f32))
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelError<f64, f32> for f32 {
+    fn rel_error(&self, expected: &f64) -> ApproEqResult<f32> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &f64, def: RelErrorDef) -> ApproEqResult<f32> {
+        rel_ratio(f64::from(*self), *expected, def).map(|v| v.map(|v| v as f32))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError<f32, f32> for f64 {
+    fn abs_error(&self, expected: &f32) -> ApproEqResult<f32> {
+        FloatPolicy::measure(*self, f64::from(*expected), 0.0, || {
+            Ok(Some((self - f64::from(*expected)).abs() as f32))
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelError<f32, f32> for f64 {
+    fn rel_error(&self, expected: &f32) -> ApproEqResult<f32> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &f32, def: RelErrorDef) -> ApproEqResult<f32> {
+        rel_ratio(*self, f64::from(*expected), def).map(|v| v.map(|v| v as f32))
+    }
+}
+
+/// The relative error of integers from the magnitudes of the difference and the values, rounded up.
+///
+/// The mean of the values is not computed, as their sum could overflow. The difference is at most
+/// the sum, so the relative error by the mean is 2 when the difference exceeds half of the sum,
+/// and otherwise 1 unless the values are equal.
+fn uint_rel_error<T>(diff: T, actual: T, expected: T, def: RelErrorDef) -> Result<T, ApproEqError>
+where
+    T: Copy + PartialOrd + From<u8> + Add<Output = T> + Div<Output = T> + Rem<Output = T>,
+{
+    let (zero, one, two) = (T::from(0), T::from(1), T::from(2));
+    let half = |a: T, b: T| a / two + b / two + (a % two + b % two) / two;
+    if def == RelErrorDef::Mean {
+        return if actual == zero && expected == zero {
+            Err(ApproEqError::DividedByZero)
+        } else if diff == zero {
+            Ok(zero)
+        } else if diff <= half(actual, expected) {
+            Ok(one)
+        } else {
+            Ok(two)
+        };
+    }
+    let base = def.base(actual, expected, half);
+    if base == zero {
+        Err(ApproEqError::DividedByZero)
+    } else if diff % base == zero {
+        Ok(diff / base)
+    } else {
+        Ok(diff / base + one)
+    }
+}
+
+/// The relative error of integers as a ratio, from the magnitudes of the difference and the values.
+fn int_rel_error(diff: f64, actual: f64, expected: f64, def: RelErrorDef) -> ApproEqResult<f64> {
+    let base = def.base(actual, expected, |a, b| (a + b) / 2.0);
+    if base == 0.0 {
+        Err(ApproEqError::DividedByZero)
+    } else {
+        Ok(Some(diff / base))
+    }
+}
+
+macro_rules! itype_impls {
+    ($($T:ty)+) => {
+        $(
+            /// tolerance is zero for $T
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl Tolerance for $T {
+                fn tolerance() -> $T {
+                    Self::queried_tolerance(Query::Tolerance)
+                }
+
+                fn queried_tolerance(query: Query) -> $T {
+                    tol::default_tolerance(query, 0)
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl AbsError for $T {
+                fn abs_error(&self, expected: &$T) -> ApproEqResult<$T> {
+                    <$T>::try_from(self.abs_diff(*expected))
+                        .map(Some)
+                        .map_err(|_| ApproEqError::Overflow)
+                }
+            }
+
+            /// The relative error rounded up, so that it is zero for equal values only.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<$T> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<$T> {
+                    let rel = uint_rel_error(self.abs_diff(*expected), self.unsigned_abs(), expected.unsigned_abs(), def)?;
+                    <$T>::try_from(rel)
+                        .map(Some)
+                        .map_err(|_| ApproEqError::Overflow)
+                }
+            }
+
+            /// The relative error as a ratio, which `RelError<$T, $T>` rounds up to an integer.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError<$T, f64> for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<f64> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<f64> {
+                    int_rel_error(self.abs_diff(*expected) as f64, self.unsigned_abs() as f64, expected.unsigned_abs() as f64, def)
+                }
+            }
+
+            /// The relative error as a ratio, which `RelError<$T, $T>` rounds up to an integer.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError<$T, f32> for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<f32> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<f32> {
+                    int_rel_error(self.abs_diff(*expected) as f64, self.unsigned_abs() as f64, expected.unsigned_abs() as f64, def).map(|ratio| ratio.map(|ratio| ratio as f32))
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl TolError for $T {
+                fn tol_error(&self, expected: &$T, tol: &Tol<$T>) -> ApproEqResult<f64> {
+                    let diff = self.abs_diff(*expected);
+                    tol.to_f64(|v| *v as f64, <$T>::abs_tolerance).ratio(
+                        diff as f64,
+                        self.unsigned_abs() as f64,
+                        expected.unsigned_abs() as f64,
+                        || {
+                            u64::try_from(diff)
+                                .map(Some)
+                                .map_err(|_| ApproEqError::Overflow)
+                        },
+                    )
+                }
+            }
+        )+
+    }
+}
+
+itype_impls! { i8 i16 i32 i64 i128 isize }
+
+macro_rules! utype_impls {
+    ($($T:ty)+) => {
+        $(
             #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
             impl Tolerance for $T {
                 fn tolerance() -> $T {
-                    0
+                    Self::queried_tolerance(Query::Tolerance)
+                }
+
+                fn queried_tolerance(query: Query) -> $T {
+                    tol::default_tolerance(query, 0)
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl AbsError for $T {
+                fn abs_error(&self, expected: &$T) -> ApproEqResult<$T> {
+                    Ok(Some(self.abs_diff(*expected)))
+                }
+            }
+
+            /// The relative error rounded up, so that it is zero for equal values only.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<$T> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<$T> {
+                    uint_rel_error(self.abs_diff(*expected), *self, *expected, def).map(Some)
+                }
+            }
+
+            /// The relative error as a ratio, which `RelError<$T, $T>` rounds up to an integer.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError<$T, f64> for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<f64> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<f64> {
+                    int_rel_error(self.abs_diff(*expected) as f64, *self as f64, *expected as f64, def)
                 }
             }
 
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl AbsError for $T {
-                fn abs_error(&self, expected: &$T) -> ApproEqResult<$T> {
-                    Ok(Some((self - expected).abs()))
-                }
-            }
+            /// The relative error as a ratio, which `RelError<$T, $T>` rounds up to an integer.
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl RelError<$T, f32> for $T {
+                fn rel_error(&self, expected: &$T) -> ApproEqResult<f32> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$T, def: RelErrorDef) -> ApproEqResult<f32> {
+                    int_rel_error(self.abs_diff(*expected) as f64, *self as f64, *expected as f64, def).map(|ratio| ratio.map(|ratio| ratio as f32))
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl TolError for $T {
+                fn tol_error(&self, expected: &$T, tol: &Tol<$T>) -> ApproEqResult<f64> {
+                    let diff = self.abs_diff(*expected);
+                    tol.to_f64(|v| *v as f64, <$T>::abs_tolerance).ratio(
+                        diff as f64,
+                        *self as f64,
+                        *expected as f64,
+                        || {
+                            u64::try_from(diff)
+                                .map(Some)
+                                .map_err(|_| ApproEqError::Overflow)
+                        },
+                    )
+                }
+            }
+        )+
+    }
+}
+
+utype_impls! { u8 u16 u32 u64 u128 usize }
+
+/// Visits the elements of two sequences of the same length with `element`.
+fn seq_visit<'a, A: 'a, B: 'a, F>(
+    actual: impl ExactSizeIterator<Item = &'a B>,
+    expected: impl ExactSizeIterator<Item = &'a A>,
+    path: &Path<'_>,
+    mut element: F,
+) -> Result<(), ApproEqError>
+where
+    F: FnMut(&B, &A, &Path<'_>) -> Result<(), ApproEqError>,
+{
+    if actual.len() != expected.len() {
+        Err(path.locate(ApproEqError::LengthMismatch))
+    } else {
+        each(
+            actual.zip(expected),
+            path,
+            Segment::Index,
+            |(a, e), path| element(a, e, path),
+        )
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: AbsError<A, D>> AbsError<[A], D> for [B] {
+    fn abs_error(&self, expected: &[A]) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &[A],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+            a.abs_visit(e, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: RelError<A, D>> RelError<[A], D> for [B] {
+    fn rel_error(&self, expected: &[A]) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &[A], def: RelErrorDef) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &[A],
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+            a.rel_visit(e, def, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: UlpsError<A, D>> UlpsError<[A], D> for [B] {
+    fn ulps_error(&self, expected: &[A]) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &[A],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+            a.ulps_visit(e, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: MixedError<A, D>> MixedError<[A], D> for [B] {
+    fn mixed_error(&self, expected: &[A], abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &[A],
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+            a.mixed_visit(e, abs_tol, rel_tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>> TolError<[A], D> for [B] {
+    fn tol_error(&self, expected: &[A], tol: &Tol<D>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &[A],
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+            a.tol_visit(e, tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: AbsError<A, D>> AbsError<Vec<A>, D> for Vec<B> {
+    fn abs_error(&self, expected: &Vec<A>) -> ApproEqResult<D> {
+        self.as_slice().abs_error(expected.as_slice())
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Vec<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .abs_visit(expected.as_slice(), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: RelError<A, D>> RelError<Vec<A>, D> for Vec<B> {
+    fn rel_error(&self, expected: &Vec<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Vec<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        self.as_slice().rel_error_with_def(expected.as_slice(), def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Vec<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .rel_visit(expected.as_slice(), def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: UlpsError<A, D>> UlpsError<Vec<A>, D> for Vec<B> {
+    fn ulps_error(&self, expected: &Vec<A>) -> ApproEqResult<D> {
+        self.as_slice().ulps_error(expected.as_slice())
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Vec<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .ulps_visit(expected.as_slice(), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: MixedError<A, D>> MixedError<Vec<A>, D> for Vec<B> {
+    fn mixed_error(&self, expected: &Vec<A>, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        self.as_slice()
+            .mixed_error(expected.as_slice(), abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &Vec<A>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .mixed_visit(expected.as_slice(), abs_tol, rel_tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>> TolError<Vec<A>, D> for Vec<B> {
+    fn tol_error(&self, expected: &Vec<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        self.as_slice().tol_error(expected.as_slice(), tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Vec<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_slice()
+            .tol_visit(expected.as_slice(), tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<'a, A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<&'a A, D> for &'a B {
+    fn abs_error(&self, expected: &&A) -> ApproEqResult<D> {
+        (*self).abs_error(expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &&A,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self).abs_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<'a, A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<&'a A, D> for &'a B {
+    fn rel_error(&self, expected: &&A) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &&A, def: RelErrorDef) -> ApproEqResult<D> {
+        (*self).rel_error_with_def(expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &&A,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self).rel_visit(expected, def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<'a, A: ?Sized, D: PartialOrd, B: UlpsError<A, D> + ?Sized> UlpsError<&'a A, D> for &'a B {
+    fn ulps_error(&self, expected: &&A) -> ApproEqResult<D> {
+        (*self).ulps_error(expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &&A,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self).ulps_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<'a, A: ?Sized, D, B: MixedError<A, D> + ?Sized> MixedError<&'a A, D> for &'a B {
+    fn mixed_error(&self, expected: &&A, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        (*self).mixed_error(expected, abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &&A,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (*self).mixed_visit(expected, abs_tol, rel_tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<'a, A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<&'a A, D> for &'a B {
+    fn tol_error(&self, expected: &&A, tol: &Tol<D>) -> ApproEqResult<f64> {
+        (*self).tol_error(expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &&A,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (*self).tol_visit(expected, tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: AbsError<A, D>, const N: usize> AbsError<[A; N], D> for [B; N] {
+    fn abs_error(&self, expected: &[A; N]) -> ApproEqResult<D> {
+        self[..].abs_error(&expected[..])
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &[A; N],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self[..].abs_visit(&expected[..], path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: RelError<A, D>, const N: usize> RelError<[A; N], D> for [B; N] {
+    fn rel_error(&self, expected: &[A; N]) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &[A; N], def: RelErrorDef) -> ApproEqResult<D> {
+        self[..].rel_error_with_def(&expected[..], def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &[A; N],
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self[..].rel_visit(&expected[..], def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D: PartialOrd, B: UlpsError<A, D>, const N: usize> UlpsError<[A; N], D> for [B; N] {
+    fn ulps_error(&self, expected: &[A; N]) -> ApproEqResult<D> {
+        self[..].ulps_error(&expected[..])
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &[A; N],
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self[..].ulps_visit(&expected[..], path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: MixedError<A, D>, const N: usize> MixedError<[A; N], D> for [B; N] {
+    fn mixed_error(&self, expected: &[A; N], abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        self[..].mixed_error(&expected[..], abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &[A; N],
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self[..].mixed_visit(&expected[..], abs_tol, rel_tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>, const N: usize> TolError<[A; N], D> for [B; N] {
+    fn tol_error(&self, expected: &[A; N], tol: &Tol<D>) -> ApproEqResult<f64> {
+        self[..].tol_error(&expected[..], tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &[A; N],
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self[..].tol_visit(&expected[..], tol, path, visitor)
+    }
+}
+
+macro_rules! seq_impls {
+    ($([$($g:tt)*] $Actual:ty, $Expected:ty;)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($g)* A, D: PartialOrd, B: AbsError<A, D>> AbsError<$Expected, D> for $Actual {
+                fn abs_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+                }
+
+                fn abs_visit(
+                    &self,
+                    expected: &$Expected,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    seq_visit(self.iter(), expected.iter(), path, |a, e, path| a.abs_visit(e, path, visitor))
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($g)* A, D: PartialOrd, B: RelError<A, D>> RelError<$Expected, D> for $Actual {
+                fn rel_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$Expected, def: RelErrorDef) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+                }
+
+                fn rel_visit(
+                    &self,
+                    expected: &$Expected,
+                    def: RelErrorDef,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    seq_visit(self.iter(), expected.iter(), path, |a, e, path| a.rel_visit(e, def, path, visitor))
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($g)* A, D: PartialOrd, B: UlpsError<A, D>> UlpsError<$Expected, D> for $Actual {
+                fn ulps_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+                }
+
+                fn ulps_visit(
+                    &self,
+                    expected: &$Expected,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    seq_visit(self.iter(), expected.iter(), path, |a, e, path| a.ulps_visit(e, path, visitor))
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($g)* A, D, B: MixedError<A, D>> MixedError<$Expected, D> for $Actual {
+                fn mixed_error(&self, expected: &$Expected, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+                    reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+                }
+
+                fn mixed_visit(
+                    &self,
+                    expected: &$Expected,
+                    abs_tol: &D,
+                    rel_tol: &D,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    seq_visit(self.iter(), expected.iter(), path, |a, e, path| {
+                        a.mixed_visit(e, abs_tol, rel_tol, path, visitor)
+                    })
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($g)* A, D, B: TolError<A, D>> TolError<$Expected, D> for $Actual {
+                fn tol_error(&self, expected: &$Expected, tol: &Tol<D>) -> ApproEqResult<f64> {
+                    reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+                }
+
+                fn tol_visit(
+                    &self,
+                    expected: &$Expected,
+                    tol: &Tol<D>,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    seq_visit(self.iter(), expected.iter(), path, |a, e, path| a.tol_visit(e, tol, path, visitor))
+                }
+            }
+        )+
+    }
+}
+
+seq_impls! {
+    [] VecDeque<B>, VecDeque<A>;
+
+    ['a,] Vec<B>, &'a [A];
+    [const N: usize,] Vec<B>, [A; N];
+    [] Vec<B>, VecDeque<A>;
+    [] Vec<B>, Box<[A]>;
+
+    ['a,] &'a [B], Vec<A>;
+    ['a, const N: usize,] &'a [B], [A; N];
+    ['a,] &'a [B], VecDeque<A>;
+    ['a,] &'a [B], Box<[A]>;
+
+    [const N: usize,] [B; N], Vec<A>;
+    ['a, const N: usize,] [B; N], &'a [A];
+    [const N: usize,] [B; N], VecDeque<A>;
+    [const N: usize,] [B; N], Box<[A]>;
+
+    [] VecDeque<B>, Vec<A>;
+    ['a,] VecDeque<B>, &'a [A];
+    [const N: usize,] VecDeque<B>, [A; N];
+    [] VecDeque<B>, Box<[A]>;
+
+    [] Box<[B]>, Vec<A>;
+    ['a,] Box<[B]>, &'a [A];
+    [const N: usize,] Box<[B]>, [A; N];
+    [] Box<[B]>, VecDeque<A>;
+}
+
+macro_rules! tuple_impls {
+    ($(($($n:tt $A:ident $B:ident),+))+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<D: PartialOrd, $($A, $B: AbsError<$A, D>),+> AbsError<($($A,)+), D> for ($($B,)+) {
+                fn abs_error(&self, expected: &($($A,)+)) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+                }
+
+                fn abs_visit(
+                    &self,
+                    expected: &($($A,)+),
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    report::support::fields(path, visitor, &[$((stringify!($n), &|path, visitor| {
+                        self.$n.abs_visit(&expected.$n, path, visitor)
+                    })),+])
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<D: PartialOrd, $($A, $B: RelError<$A, D>),+> RelError<($($A,)+), D> for ($($B,)+) {
+                fn rel_error(&self, expected: &($($A,)+)) -> ApproEqResult<D> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &($($A,)+), def: RelErrorDef) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+                }
+
+                fn rel_visit(
+                    &self,
+                    expected: &($($A,)+),
+                    def: RelErrorDef,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    report::support::fields(path, visitor, &[$((stringify!($n), &|path, visitor| {
+                        self.$n.rel_visit(&expected.$n, def, path, visitor)
+                    })),+])
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<D: PartialOrd, $($A, $B: UlpsError<$A, D>),+> UlpsError<($($A,)+), D> for ($($B,)+) {
+                fn ulps_error(&self, expected: &($($A,)+)) -> ApproEqResult<D> {
+                    reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+                }
+
+                fn ulps_visit(
+                    &self,
+                    expected: &($($A,)+),
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    report::support::fields(path, visitor, &[$((stringify!($n), &|path, visitor| {
+                        self.$n.ulps_visit(&expected.$n, path, visitor)
+                    })),+])
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<D, $($A, $B: MixedError<$A, D>),+> MixedError<($($A,)+), D> for ($($B,)+) {
+                fn mixed_error(&self, expected: &($($A,)+), abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+                    reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+                }
+
+                fn mixed_visit(
+                    &self,
+                    expected: &($($A,)+),
+                    abs_tol: &D,
+                    rel_tol: &D,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    report::support::fields(path, visitor, &[$((stringify!($n), &|path, visitor| {
+                        self.$n.mixed_visit(&expected.$n, abs_tol, rel_tol, path, visitor)
+                    })),+])
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<D, $($A, $B: TolError<$A, D>),+> TolError<($($A,)+), D> for ($($B,)+) {
+                fn tol_error(&self, expected: &($($A,)+), tol: &Tol<D>) -> ApproEqResult<f64> {
+                    reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+                }
+
+                fn tol_visit(
+                    &self,
+                    expected: &($($A,)+),
+                    tol: &Tol<D>,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    report::support::fields(path, visitor, &[$((stringify!($n), &|path, visitor| {
+                        self.$n.tol_visit(&expected.$n, tol, path, visitor)
+                    })),+])
+                }
+            }
+        )+
+    }
+}
+
+tuple_impls! {
+    (0 A0 B0)
+    (0 A0 B0, 1 A1 B1)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6, 7 A7 B7)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6, 7 A7 B7, 8 A8 B8)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6, 7 A7 B7, 8 A8 B8, 9 A9 B9)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6, 7 A7 B7, 8 A8 B8, 9 A9 B9, 10 A10 B10)
+    (0 A0 B0, 1 A1 B1, 2 A2 B2, 3 A3 B3, 4 A4 B4, 5 A5 B5, 6 A6 B6, 7 A7 B7, 8 A8 B8, 9 A9 B9, 10 A10 B10, 11 A11 B11)
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: AbsError<A, D>> AbsError<Option<A>, D> for Option<B> {
+    fn abs_error(&self, expected: &Option<A>) -> ApproEqResult<D> {
+        match (self, expected) {
+            (None, None) => Ok(None),
+            (None, _) | (_, None) => Err(ApproEqError::NonNumDifference),
+            (Some(x), Some(y)) => x.abs_error(y),
+        }
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Option<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (None, _) | (_, None) => Err(path.locate(ApproEqError::NonNumDifference)),
+            (Some(x), Some(y)) => x.abs_visit(y, path, visitor),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: RelError<A, D>> RelError<Option<A>, D> for Option<B> {
+    fn rel_error(&self, expected: &Option<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Option<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        match (self, expected) {
+            (None, None) => Ok(None),
+            (None, _) | (_, None) => Err(ApproEqError::NonNumDifference),
+            (Some(x), Some(y)) => x.rel_error_with_def(y, def),
+        }
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Option<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (None, _) | (_, None) => Err(path.locate(ApproEqError::NonNumDifference)),
+            (Some(x), Some(y)) => x.rel_visit(y, def, path, visitor),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: UlpsError<A, D>> UlpsError<Option<A>, D> for Option<B> {
+    fn ulps_error(&self, expected: &Option<A>) -> ApproEqResult<D> {
+        match (self, expected) {
+            (None, None) => Ok(None),
+            (None, _) | (_, None) => Err(ApproEqError::NonNumDifference),
+            (Some(x), Some(y)) => x.ulps_error(y),
+        }
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Option<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (None, _) | (_, None) => Err(path.locate(ApproEqError::NonNumDifference)),
+            (Some(x), Some(y)) => x.ulps_visit(y, path, visitor),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: MixedError<A, D>> MixedError<Option<A>, D> for Option<B> {
+    fn mixed_error(&self, expected: &Option<A>, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        match (self, expected) {
+            (None, None) => Ok(None),
+            (None, _) | (_, None) => Err(ApproEqError::NonNumDifference),
+            (Some(x), Some(y)) => x.mixed_error(y, abs_tol, rel_tol),
+        }
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &Option<A>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (None, _) | (_, None) => Err(path.locate(ApproEqError::NonNumDifference)),
+            (Some(x), Some(y)) => x.mixed_visit(y, abs_tol, rel_tol, path, visitor),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, D, B: TolError<A, D>> TolError<Option<A>, D> for Option<B> {
+    fn tol_error(&self, expected: &Option<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        match (self, expected) {
+            (None, None) => Ok(None),
+            (None, _) | (_, None) => Err(ApproEqError::NonNumDifference),
+            (Some(x), Some(y)) => x.tol_error(y, tol),
+        }
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Option<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (None, _) | (_, None) => Err(path.locate(ApproEqError::NonNumDifference)),
+            (Some(x), Some(y)) => x.tol_visit(y, tol, path, visitor),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Rc<A>, D> for Rc<B> {
+    fn abs_error(&self, expected: &Rc<A>) -> ApproEqResult<D> {
+        self.as_ref().abs_error(expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Rc<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().abs_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Rc<A>, D> for Rc<B> {
+    fn rel_error(&self, expected: &Rc<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Rc<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        self.as_ref().rel_error_with_def(expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Rc<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().rel_visit(expected, def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: UlpsError<A, D> + ?Sized> UlpsError<Rc<A>, D> for Rc<B> {
+    fn ulps_error(&self, expected: &Rc<A>) -> ApproEqResult<D> {
+        self.as_ref().ulps_error(expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Rc<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().ulps_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Arc<A>, D> for Arc<B> {
+    fn abs_error(&self, expected: &Arc<A>) -> ApproEqResult<D> {
+        self.as_ref().abs_error(expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Arc<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().abs_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Arc<A>, D> for Arc<B> {
+    fn rel_error(&self, expected: &Arc<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Arc<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        self.as_ref().rel_error_with_def(expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Arc<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().rel_visit(expected, def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: UlpsError<A, D> + ?Sized> UlpsError<Arc<A>, D> for Arc<B> {
+    fn ulps_error(&self, expected: &Arc<A>) -> ApproEqResult<D> {
+        self.as_ref().ulps_error(expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Arc<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().ulps_visit(expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Weak<A>, D> for Weak<B> {
+    fn abs_error(&self, expected: &Weak<A>) -> ApproEqResult<D> {
+        self.upgrade().abs_error(&expected.upgrade())
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Weak<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.upgrade().abs_visit(&expected.upgrade(), path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Weak<A>, D> for Weak<B> {
+    fn rel_error(&self, expected: &Weak<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Weak<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        self.upgrade().rel_error_with_def(&expected.upgrade(), def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Weak<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.upgrade()
+            .rel_visit(&expected.upgrade(), def, path, visitor)
+    }
+}
 
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl RelError for $T {
-                fn rel_error(&self, expected: &$T) -> ApproEqResult<$T> {
-                    if *expected == 0 {
-                        Err(ApproEqError::DividedByZero)
-                    } else {
-                        Ok(Some((self - expected).abs() / expected))
-                    }
-                }
-            }
-        )+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D: PartialOrd, B: UlpsError<A, D> + ?Sized> UlpsError<Weak<A>, D> for Weak<B> {
+    fn ulps_error(&self, expected: &Weak<A>) -> ApproEqResult<D> {
+        self.upgrade().ulps_error(&expected.upgrade())
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Weak<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        self.upgrade()
+            .ulps_visit(&expected.upgrade(), path, visitor)
     }
 }
 
-itype_impls! { i8 i16 i32 i64 i128 }
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: Copy, D: PartialOrd, B: AbsError<A, D> + Copy> AbsError<Cell<A>, D> for Cell<B> {
+    fn abs_error(&self, expected: &Cell<A>) -> ApproEqResult<D> {
+        (*self).get().abs_error(&(*expected).get())
+    }
 
-macro_rules! utype_impls {
-    ($($T:ty)+) => {
-        $(
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl Tolerance for $T {
-                fn tolerance() -> $T {
-                    0
-                }
-            }
+    fn abs_visit(
+        &self,
+        expected: &Cell<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self).get().abs_visit(&(*expected).get(), path, visitor)
+    }
+}
 
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl AbsError for $T {
-                fn abs_error(&self, expected: &$T) -> ApproEqResult<$T> {
-                    Ok(Some(if *self > *expected { *self - *expected } else { *expected - *self }))
-                }
-            }
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: Copy, D: PartialOrd, B: RelError<A, D> + Copy> RelError<Cell<A>, D> for Cell<B> {
+    fn rel_error(&self, expected: &Cell<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
 
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl RelError for $T {
-                fn rel_error(&self, expected: &$T) -> ApproEqResult<$T> {
-                    if *expected == 0 {
-                        Err(ApproEqError::DividedByZero)
-                    } else {
-                        Ok(Some((if *self > *expected { *self - *expected } else { *expected - *self }) / expected))
-                    }
-                }
-            }
-        )+
+    fn rel_error_with_def(&self, expected: &Cell<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        (*self).get().rel_error_with_def(&(*expected).get(), def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Cell<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .get()
+            .rel_visit(&(*expected).get(), def, path, visitor)
     }
 }
 
-utype_impls! { u8 u16 u32 u64 u128 }
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: Copy, D: PartialOrd, B: UlpsError<A, D> + Copy> UlpsError<Cell<A>, D> for Cell<B> {
+    fn ulps_error(&self, expected: &Cell<A>) -> ApproEqResult<D> {
+        (*self).get().ulps_error(&(*expected).get())
+    }
 
-fn max<D: PartialOrd, T: Iterator<Item = ApproEqResult<D>>>(iter: T) -> ApproEqResult<D> {
-    iter.fold(Ok(None), move |m, i| {
-        if match (&m, &i) {
-            (&Err(_), _) => false,
-            (_, &Err(_)) => true,
-            (&Ok(ref m), &Ok(ref i)) => match (m, i) {
-                (&None, _) => true,
-                (_, &None) => false,
-                (&Some(ref m), &Some(ref i)) => i > m,
-            },
-        } {
-            i
-        } else {
-            m
-        }
-    })
+    fn ulps_visit(
+        &self,
+        expected: &Cell<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self).get().ulps_visit(&(*expected).get(), path, visitor)
+    }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D: PartialOrd, B: AbsError<A, D>> AbsError<[A], D> for [B] {
-    fn abs_error(&self, expected: &[A]) -> ApproEqResult<D> {
-        if self.len() != expected.len() {
-            Err(ApproEqError::LengthMismatch)
-        } else {
-            max((0..self.len()).map(|i| self[i].abs_error(&expected[i])))
-        }
+impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<RefCell<A>, D> for RefCell<B> {
+    fn abs_error(&self, expected: &RefCell<A>) -> ApproEqResult<D> {
+        (*self).borrow().abs_error(&(*expected).borrow())
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &RefCell<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .borrow()
+            .abs_visit(&(*expected).borrow(), path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D: PartialOrd, B: RelError<A, D>> RelError<[A], D> for [B] {
-    fn rel_error(&self, expected: &[A]) -> ApproEqResult<D> {
-        if self.len() != expected.len() {
-            Err(ApproEqError::LengthMismatch)
-        } else {
-            max((0..self.len()).map(|i| self[i].rel_error(&expected[i])))
-        }
+impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<RefCell<A>, D> for RefCell<B> {
+    fn rel_error(&self, expected: &RefCell<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &RefCell<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        (*self)
+            .borrow()
+            .rel_error_with_def(&(*expected).borrow(), def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &RefCell<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .borrow()
+            .rel_visit(&(*expected).borrow(), def, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D: PartialOrd, B: AbsError<A, D>> AbsError<Vec<A>, D> for Vec<B> {
-    fn abs_error(&self, expected: &Vec<A>) -> ApproEqResult<D> {
-        if self.len() != expected.len() {
-            Err(ApproEqError::LengthMismatch)
-        } else {
-            max((0..self.len()).map(|i| self[i].abs_error(&expected[i])))
-        }
+impl<A: ?Sized, D: PartialOrd, B: UlpsError<A, D> + ?Sized> UlpsError<RefCell<A>, D>
+    for RefCell<B>
+{
+    fn ulps_error(&self, expected: &RefCell<A>) -> ApproEqResult<D> {
+        (*self).borrow().ulps_error(&(*expected).borrow())
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &RefCell<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .borrow()
+            .ulps_visit(&(*expected).borrow(), path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D: PartialOrd, B: RelError<A, D>> RelError<Vec<A>, D> for Vec<B> {
-    fn rel_error(&self, expected: &Vec<A>) -> ApproEqResult<D> {
-        if self.len() != expected.len() {
-            Err(ApproEqError::LengthMismatch)
-        } else {
-            max((0..self.len()).map(|i| self[i].rel_error(&expected[i])))
-        }
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<Rc<A>, D> for Rc<B> {
+    fn tol_error(&self, expected: &Rc<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        self.as_ref().tol_error(expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Rc<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().tol_visit(expected, tol, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<'a, A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<&'a A, D> for &'a B {
-    fn abs_error(&self, expected: &&A) -> ApproEqResult<D> {
-        (*self).abs_error(expected)
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<Arc<A>, D> for Arc<B> {
+    fn tol_error(&self, expected: &Arc<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        self.as_ref().tol_error(expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Arc<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.as_ref().tol_visit(expected, tol, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<'a, A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<&'a A, D> for &'a B {
-    fn rel_error(&self, expected: &&A) -> ApproEqResult<D> {
-        (*self).rel_error(expected)
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<Weak<A>, D> for Weak<B> {
+    fn tol_error(&self, expected: &Weak<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        self.upgrade().tol_error(&expected.upgrade(), tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Weak<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        self.upgrade()
+            .tol_visit(&expected.upgrade(), tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: Copy, D, B: TolError<A, D> + Copy> TolError<Cell<A>, D> for Cell<B> {
+    fn tol_error(&self, expected: &Cell<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        (*self).get().tol_error(&(*expected).get(), tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Cell<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .get()
+            .tol_visit(&(*expected).get(), tol, path, visitor)
     }
 }
 
-macro_rules! array_impls {
-    ($($N:expr)+) => {
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<RefCell<A>, D> for RefCell<B> {
+    fn tol_error(&self, expected: &RefCell<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        (*self).borrow().tol_error(&(*expected).borrow(), tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &RefCell<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (*self)
+            .borrow()
+            .tol_visit(&(*expected).borrow(), tol, path, visitor)
+    }
+}
+
+macro_rules! deref_impls {
+    ($(<$($lt:lifetime),*> $Actual:ty, $Expected:ty $(, $bound:path)*;)+) => {
         $(
             #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl<A, D: PartialOrd, B: AbsError<A, D>> AbsError<[A; $N], D> for [B; $N] {
-                fn abs_error(&self, expected: &[A; $N]) -> ApproEqResult<D>{
-                    max((0..self.len()).map(|i| self[i].abs_error(&expected[i])))
+            impl<$($lt,)* A: ?Sized $(+ $bound)*, D, B: AbsError<A, D> + ?Sized $(+ $bound)*> AbsError<$Expected, D> for $Actual {
+                fn abs_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    (**self).abs_error(&**expected)
+                }
+
+                fn abs_visit(
+                    &self,
+                    expected: &$Expected,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    (**self).abs_visit(&**expected, path, visitor)
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($lt,)* A: ?Sized $(+ $bound)*, D, B: RelError<A, D> + ?Sized $(+ $bound)*> RelError<$Expected, D> for $Actual {
+                fn rel_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    self.rel_error_with_def(expected, RelErrorDef::default())
+                }
+
+                fn rel_error_with_def(&self, expected: &$Expected, def: RelErrorDef) -> ApproEqResult<D> {
+                    (**self).rel_error_with_def(&**expected, def)
+                }
+
+                fn rel_visit(
+                    &self,
+                    expected: &$Expected,
+                    def: RelErrorDef,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    (**self).rel_visit(&**expected, def, path, visitor)
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($lt,)* A: ?Sized $(+ $bound)*, D, B: UlpsError<A, D> + ?Sized $(+ $bound)*> UlpsError<$Expected, D> for $Actual {
+                fn ulps_error(&self, expected: &$Expected) -> ApproEqResult<D> {
+                    (**self).ulps_error(&**expected)
+                }
+
+                fn ulps_visit(
+                    &self,
+                    expected: &$Expected,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<D>,
+                ) -> Result<(), ApproEqError> {
+                    (**self).ulps_visit(&**expected, path, visitor)
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            impl<$($lt,)* A: ?Sized $(+ $bound)*, D, B: MixedError<A, D> + ?Sized $(+ $bound)*> MixedError<$Expected, D> for $Actual {
+                fn mixed_error(&self, expected: &$Expected, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+                    (**self).mixed_error(&**expected, abs_tol, rel_tol)
+                }
+
+                fn mixed_visit(
+                    &self,
+                    expected: &$Expected,
+                    abs_tol: &D,
+                    rel_tol: &D,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    (**self).mixed_visit(&**expected, abs_tol, rel_tol, path, visitor)
                 }
             }
 
             #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-            impl<A, D: PartialOrd, B: RelError<A, D>> RelError<[A; $N], D> for [B; $N] {
-                fn rel_error(&self, expected: &[A; $N]) -> ApproEqResult<D>{
-                    max((0..self.len()).map(|i| self[i].rel_error(&expected[i])))
+            impl<$($lt,)* A: ?Sized $(+ $bound)*, D, B: TolError<A, D> + ?Sized $(+ $bound)*> TolError<$Expected, D> for $Actual {
+                fn tol_error(&self, expected: &$Expected, tol: &Tol<D>) -> ApproEqResult<f64> {
+                    (**self).tol_error(&**expected, tol)
+                }
+
+                fn tol_visit(
+                    &self,
+                    expected: &$Expected,
+                    tol: &Tol<D>,
+                    path: &Path<'_>,
+                    visitor: &mut dyn Visitor<f64>,
+                ) -> Result<(), ApproEqError> {
+                    (**self).tol_visit(&**expected, tol, path, visitor)
                 }
             }
         )+
     }
 }
 
-array_impls! {
-     0  1  2  3  4  5  6  7  8  9
-    10 11 12 13 14 15 16 17 18 19
-    20 21 22 23 24 25 26 27 28 29
-    30 31 32
+deref_impls! {
+    <> Box<B>, Box<A>;
+    <'a, 'b> Cow<'a, B>, Cow<'b, A>, ToOwned;
+    <'a, 'b> &'a mut B, &'b mut A;
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D, B: AbsError<A, D>> AbsError<Option<A>, D> for Option<B> {
-    fn abs_error(&self, expected: &Option<A>) -> ApproEqResult<D> {
-        match (self, expected) {
-            (&None, &None) => Ok(None),
-            (&None, _) | (_, &None) => Err(ApproEqError::NonNumDifference),
-            (&Some(ref x), &Some(ref y)) => x.abs_error(y),
-        }
+impl<D, P: Deref, Q: Deref> AbsError<Pin<Q>, D> for Pin<P>
+where
+    P::Target: AbsError<Q::Target, D>,
+{
+    fn abs_error(&self, expected: &Pin<Q>) -> ApproEqResult<D> {
+        (**self).abs_error(&**expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Pin<Q>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (**self).abs_visit(&**expected, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, D, B: RelError<A, D>> RelError<Option<A>, D> for Option<B> {
-    fn rel_error(&self, expected: &Option<A>) -> ApproEqResult<D> {
-        match (self, expected) {
-            (&None, &None) => Ok(None),
-            (&None, _) | (_, &None) => Err(ApproEqError::NonNumDifference),
-            (&Some(ref x), &Some(ref y)) => x.rel_error(y),
-        }
+impl<D, P: Deref, Q: Deref> RelError<Pin<Q>, D> for Pin<P>
+where
+    P::Target: RelError<Q::Target, D>,
+{
+    fn rel_error(&self, expected: &Pin<Q>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Pin<Q>, def: RelErrorDef) -> ApproEqResult<D> {
+        (**self).rel_error_with_def(&**expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Pin<Q>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (**self).rel_visit(&**expected, def, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Rc<A>, D> for Rc<B> {
-    fn abs_error(&self, expected: &Rc<A>) -> ApproEqResult<D> {
-        self.as_ref().abs_error(expected)
+impl<D, P: Deref, Q: Deref> UlpsError<Pin<Q>, D> for Pin<P>
+where
+    P::Target: UlpsError<Q::Target, D>,
+{
+    fn ulps_error(&self, expected: &Pin<Q>) -> ApproEqResult<D> {
+        (**self).ulps_error(&**expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Pin<Q>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        (**self).ulps_visit(&**expected, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Rc<A>, D> for Rc<B> {
-    fn rel_error(&self, expected: &Rc<A>) -> ApproEqResult<D> {
-        self.as_ref().rel_error(expected)
+impl<D, P: Deref, Q: Deref> MixedError<Pin<Q>, D> for Pin<P>
+where
+    P::Target: MixedError<Q::Target, D>,
+{
+    fn mixed_error(&self, expected: &Pin<Q>, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        (**self).mixed_error(&**expected, abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &Pin<Q>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (**self).mixed_visit(&**expected, abs_tol, rel_tol, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Arc<A>, D> for Arc<B> {
-    fn abs_error(&self, expected: &Arc<A>) -> ApproEqResult<D> {
-        self.as_ref().abs_error(expected)
+impl<D, P: Deref, Q: Deref> TolError<Pin<Q>, D> for Pin<P>
+where
+    P::Target: TolError<Q::Target, D>,
+{
+    fn tol_error(&self, expected: &Pin<Q>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        (**self).tol_error(&**expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Pin<Q>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        (**self).tol_visit(&**expected, tol, path, visitor)
+    }
+}
+
+/// Locks `actual` and `expected` in the order of their addresses, so that comparisons in both directions
+/// cannot deadlock, failing with `ApproEqError::Poisoned` if either is poisoned. The same lock is locked once
+/// and then tried, failing with `ApproEqError::Locked`.
+fn lock<'a, B: ?Sized, A: ?Sized>(
+    actual: &'a Mutex<B>,
+    expected: &'a Mutex<A>,
+) -> Result<(MutexGuard<'a, B>, MutexGuard<'a, A>), ApproEqError> {
+    if addr(actual) == addr(expected) {
+        let actual = actual.lock().map_err(poisoned)?;
+        Ok((actual, expected.try_lock().map_err(try_lock_error)?))
+    } else if addr(actual) < addr(expected) {
+        let actual = actual.lock().map_err(poisoned)?;
+        Ok((actual, expected.lock().map_err(poisoned)?))
+    } else {
+        let expected = expected.lock().map_err(poisoned)?;
+        Ok((actual.lock().map_err(poisoned)?, expected))
+    }
+}
+
+/// Read-locks `actual` and `expected` in the order of their addresses, as `lock` does.
+/// The same lock is read-locked once and then tried, which fails with `ApproEqError::Locked`
+/// only if a writer is waiting for it.
+fn read<'a, B: ?Sized, A: ?Sized>(
+    actual: &'a RwLock<B>,
+    expected: &'a RwLock<A>,
+) -> Result<(RwLockReadGuard<'a, B>, RwLockReadGuard<'a, A>), ApproEqError> {
+    if addr(actual) == addr(expected) {
+        let actual = actual.read().map_err(poisoned)?;
+        Ok((actual, expected.try_read().map_err(try_lock_error)?))
+    } else if addr(actual) < addr(expected) {
+        let actual = actual.read().map_err(poisoned)?;
+        Ok((actual, expected.read().map_err(poisoned)?))
+    } else {
+        let expected = expected.read().map_err(poisoned)?;
+        Ok((actual.read().map_err(poisoned)?, expected))
+    }
+}
+
+/// `ApproEqError::Poisoned`, for a lock poisoned.
+fn poisoned<T>(_: PoisonError<T>) -> ApproEqError {
+    ApproEqError::Poisoned
+}
+
+/// The error of a lock that could not be tried.
+fn try_lock_error<T>(e: TryLockError<T>) -> ApproEqError {
+    match e {
+        TryLockError::Poisoned(_) => ApproEqError::Poisoned,
+        TryLockError::WouldBlock => ApproEqError::Locked,
     }
 }
 
+/// The address of `lock`, ordering the locks compared.
+fn addr<T: ?Sized>(lock: &T) -> usize {
+    lock as *const T as *const u8 as usize
+}
+
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Arc<A>, D> for Arc<B> {
-    fn rel_error(&self, expected: &Arc<A>) -> ApproEqResult<D> {
-        self.as_ref().rel_error(expected)
+impl<A: ?Sized, D, B: AbsError<A, D> + ?Sized> AbsError<Mutex<A>, D> for Mutex<B> {
+    fn abs_error(&self, expected: &Mutex<A>) -> ApproEqResult<D> {
+        let (actual, expected) = lock(self, expected)?;
+        (*actual).abs_error(&*expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &Mutex<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = lock(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).abs_visit(&*expected, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<Weak<A>, D> for Weak<B> {
-    fn abs_error(&self, expected: &Weak<A>) -> ApproEqResult<D> {
-        self.upgrade().abs_error(&expected.upgrade())
+impl<A: ?Sized, D, B: RelError<A, D> + ?Sized> RelError<Mutex<A>, D> for Mutex<B> {
+    fn rel_error(&self, expected: &Mutex<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Mutex<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        let (actual, expected) = lock(self, expected)?;
+        (*actual).rel_error_with_def(&*expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &Mutex<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = lock(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).rel_visit(&*expected, def, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<Weak<A>, D> for Weak<B> {
-    fn rel_error(&self, expected: &Weak<A>) -> ApproEqResult<D> {
-        self.upgrade().rel_error(&expected.upgrade())
+impl<A: ?Sized, D, B: UlpsError<A, D> + ?Sized> UlpsError<Mutex<A>, D> for Mutex<B> {
+    fn ulps_error(&self, expected: &Mutex<A>) -> ApproEqResult<D> {
+        let (actual, expected) = lock(self, expected)?;
+        (*actual).ulps_error(&*expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &Mutex<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = lock(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).ulps_visit(&*expected, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: Copy + ?Sized, D: PartialOrd, B: AbsError<A, D> + Copy + ?Sized> AbsError<Cell<A>, D>
-    for Cell<B>
-{
-    fn abs_error(&self, expected: &Cell<A>) -> ApproEqResult<D> {
-        (*self).get().abs_error(&(*expected).get())
+impl<A: ?Sized, D, B: MixedError<A, D> + ?Sized> MixedError<Mutex<A>, D> for Mutex<B> {
+    fn mixed_error(&self, expected: &Mutex<A>, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        let (actual, expected) = lock(self, expected)?;
+        (*actual).mixed_error(&*expected, abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &Mutex<A>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = lock(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).mixed_visit(&*expected, abs_tol, rel_tol, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: Copy + ?Sized, D: PartialOrd, B: RelError<A, D> + Copy + ?Sized> RelError<Cell<A>, D>
-    for Cell<B>
-{
-    fn rel_error(&self, expected: &Cell<A>) -> ApproEqResult<D> {
-        (*self).get().rel_error(&(*expected).get())
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<Mutex<A>, D> for Mutex<B> {
+    fn tol_error(&self, expected: &Mutex<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        let (actual, expected) = lock(self, expected)?;
+        (*actual).tol_error(&*expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &Mutex<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = lock(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).tol_visit(&*expected, tol, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: AbsError<A, D> + ?Sized> AbsError<RefCell<A>, D> for RefCell<B> {
-    fn abs_error(&self, expected: &RefCell<A>) -> ApproEqResult<D> {
-        (*self).borrow().abs_error(&(*expected).borrow())
+impl<A: ?Sized, D, B: AbsError<A, D> + ?Sized> AbsError<RwLock<A>, D> for RwLock<B> {
+    fn abs_error(&self, expected: &RwLock<A>) -> ApproEqResult<D> {
+        let (actual, expected) = read(self, expected)?;
+        (*actual).abs_error(&*expected)
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &RwLock<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = read(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).abs_visit(&*expected, path, visitor)
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<RefCell<A>, D> for RefCell<B> {
-    fn rel_error(&self, expected: &RefCell<A>) -> ApproEqResult<D> {
-        (*self).borrow().rel_error(&(*expected).borrow())
+impl<A: ?Sized, D, B: RelError<A, D> + ?Sized> RelError<RwLock<A>, D> for RwLock<B> {
+    fn rel_error(&self, expected: &RwLock<A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &RwLock<A>, def: RelErrorDef) -> ApproEqResult<D> {
+        let (actual, expected) = read(self, expected)?;
+        (*actual).rel_error_with_def(&*expected, def)
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &RwLock<A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = read(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).rel_visit(&*expected, def, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D, B: UlpsError<A, D> + ?Sized> UlpsError<RwLock<A>, D> for RwLock<B> {
+    fn ulps_error(&self, expected: &RwLock<A>) -> ApproEqResult<D> {
+        let (actual, expected) = read(self, expected)?;
+        (*actual).ulps_error(&*expected)
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &RwLock<A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = read(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).ulps_visit(&*expected, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D, B: MixedError<A, D> + ?Sized> MixedError<RwLock<A>, D> for RwLock<B> {
+    fn mixed_error(&self, expected: &RwLock<A>, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+        let (actual, expected) = read(self, expected)?;
+        (*actual).mixed_error(&*expected, abs_tol, rel_tol)
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &RwLock<A>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = read(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).mixed_visit(&*expected, abs_tol, rel_tol, path, visitor)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A: ?Sized, D, B: TolError<A, D> + ?Sized> TolError<RwLock<A>, D> for RwLock<B> {
+    fn tol_error(&self, expected: &RwLock<A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        let (actual, expected) = read(self, expected)?;
+        (*actual).tol_error(&*expected, tol)
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &RwLock<A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let (actual, expected) = read(self, expected).map_err(|e| path.locate(e))?;
+        (*actual).tol_visit(&*expected, tol, path, visitor)
     }
 }
 
@@ -607,15 +2944,25 @@ impl<A: ?Sized, D: PartialOrd, B: RelError<A, D> + ?Sized> RelError<RefCell<A>,
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl AbsTolerance for Duration {
     fn abs_tolerance() -> Duration {
-        Duration::new(1, 0) // 1s
+        tol::default_tolerance(Query::Abs, Duration::new(1, 0)) // 1s
+    }
+}
+
+/// relative tolerance is the ratio 1/1000 for Duration,
+/// overridden and configured as a `Duration` counting a ratio of 1 as one second
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelTolerance<f64> for Duration {
+    fn rel_tolerance() -> f64 {
+        tol::configured(Query::Rel).map_or(1.0 / 1000.0, |rel: Duration| rel.as_secs_f64())
     }
 }
 
-/// relative tolerance is 1ms(1/1000) for Duration
+/// relative tolerance of `RelError<Duration, Duration>`,
+/// the ratio as a `Duration` counting a ratio of 1 as one second
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl RelTolerance for Duration {
     fn rel_tolerance() -> Duration {
-        Duration::new(0, 1_000_000) // 1ms (1/1000)
+        Duration::from_secs_f64(<Duration as RelTolerance<f64>>::rel_tolerance())
     }
 }
 
@@ -649,27 +2996,62 @@ impl AbsError<SystemTime, Duration> for SystemTime {
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl AbsError for Duration {
     fn abs_error(&self, expected: &Duration) -> ApproEqResult<Duration> {
-        Ok(Some(if *self > *expected {
-            *self - *expected
-        } else {
-            *expected - *self
-        }))
+        Ok(Some(self.abs_diff(*expected)))
     }
 }
 
+/// The relative error as a `Duration` counting a ratio of 1 as one second.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 impl RelError for Duration {
     fn rel_error(&self, expected: &Duration) -> ApproEqResult<Duration> {
-        if *expected == Duration::new(0, 0) || expected.as_secs() > u64::from(std::u32::MAX) {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Duration, def: RelErrorDef) -> ApproEqResult<Duration> {
+        match RelError::<Duration, f64>::rel_error_with_def(self, expected, def)? {
+            Some(ratio) => Duration::try_from_secs_f64(ratio)
+                .map(Some)
+                .map_err(|_| ApproEqError::Overflow),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The relative error as a ratio.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl RelError<Duration, f64> for Duration {
+    fn rel_error(&self, expected: &Duration) -> ApproEqResult<f64> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Duration, def: RelErrorDef) -> ApproEqResult<f64> {
+        let base = def.base(
+            self.as_nanos() as f64,
+            expected.as_nanos() as f64,
+            |a, b| (a + b) / 2.0,
+        );
+        if base == 0.0 {
             Err(ApproEqError::DividedByZero)
         } else {
-            Ok(Some(
-                (if *self > *expected {
-                    *self - *expected
-                } else {
-                    *expected - *self
-                }) / expected.as_secs() as u32,
-            ))
+            Ok(Some(self.abs_diff(*expected).as_nanos() as f64 / base))
         }
     }
 }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl TolError for Duration {
+    fn tol_error(&self, expected: &Duration, tol: &Tol<Duration>) -> ApproEqResult<f64> {
+        let diff = self.abs_diff(*expected);
+        tol.to_f64(Duration::as_secs_f64, Duration::abs_tolerance)
+            .ratio(
+                diff.as_secs_f64(),
+                self.as_secs_f64(),
+                expected.as_secs_f64(),
+                || {
+                    u64::try_from(diff.as_nanos())
+                        .map(Some)
+                        .map_err(|_| ApproEqError::Overflow)
+                },
+            )
+    }
+}
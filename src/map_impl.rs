@@ -0,0 +1,352 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::report::reduce;
+use crate::visit::{each, Path, Visitor};
+use crate::AbsError;
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::MixedError;
+use crate::RelError;
+use crate::RelErrorDef;
+use crate::Segment;
+use crate::Tol;
+use crate::TolError;
+use crate::UlpsError;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+/// A map whose values are compared by key.
+trait Keyed<K, V> {
+    fn entries(&self) -> Vec<(&K, &V)>;
+    fn value(&self, key: &K) -> Option<&V>;
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> Keyed<K, V> for HashMap<K, V, S> {
+    fn entries(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+
+    fn value(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+impl<K: Ord, V> Keyed<K, V> for BTreeMap<K, V> {
+    fn entries(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+
+    fn value(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+fn names<'a, K: fmt::Debug + 'a>(keys: impl Iterator<Item = &'a K>) -> Vec<String> {
+    let mut names: Vec<String> = keys.map(|key| format!("{:?}", key)).collect();
+    names.sort();
+    names
+}
+
+/// Visits the pairs of values of the same key with `element`,
+/// or fails with `ApproEqError::KeyMismatch` unless the maps have the same keys.
+fn by_key<K, A, B, F>(
+    actual: &impl Keyed<K, B>,
+    expected: &impl Keyed<K, A>,
+    path: &Path<'_>,
+    mut element: F,
+) -> Result<(), ApproEqError>
+where
+    K: fmt::Debug,
+    F: FnMut(&B, &A, &Path<'_>) -> Result<(), ApproEqError>,
+{
+    let mut pairs = Vec::new();
+    let mut extra = Vec::new();
+    for (key, value) in actual.entries() {
+        match expected.value(key) {
+            Some(expected) => pairs.push((key, value, expected)),
+            None => extra.push(key),
+        }
+    }
+    let missing = expected
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| actual.value(key).is_none())
+        .map(|(key, _)| key);
+    let missing = names(missing);
+    if !missing.is_empty() || !extra.is_empty() {
+        return Err(path.locate(ApproEqError::KeyMismatch {
+            missing,
+            extra: names(extra.into_iter()),
+        }));
+    }
+
+    let segment = |i: usize| Segment::Key(format!("{:?}", pairs[i].0));
+    each(pairs.iter(), path, segment, |(_, a, b), path| {
+        element(a, b, path)
+    })
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D, S> AbsError<HashMap<K, A, S>, D> for HashMap<K, B, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    B: AbsError<A, D>,
+    D: PartialOrd,
+    S: BuildHasher,
+{
+    fn abs_error(&self, expected: &HashMap<K, A, S>) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &HashMap<K, A, S>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.abs_visit(b, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D, S> RelError<HashMap<K, A, S>, D> for HashMap<K, B, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    B: RelError<A, D>,
+    D: PartialOrd,
+    S: BuildHasher,
+{
+    fn rel_error(&self, expected: &HashMap<K, A, S>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(
+        &self,
+        expected: &HashMap<K, A, S>,
+        def: RelErrorDef,
+    ) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &HashMap<K, A, S>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.rel_visit(b, def, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D, S> UlpsError<HashMap<K, A, S>, D> for HashMap<K, B, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    B: UlpsError<A, D>,
+    D: PartialOrd,
+    S: BuildHasher,
+{
+    fn ulps_error(&self, expected: &HashMap<K, A, S>) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &HashMap<K, A, S>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.ulps_visit(b, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D, S> MixedError<HashMap<K, A, S>, D> for HashMap<K, B, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    B: MixedError<A, D>,
+    S: BuildHasher,
+{
+    fn mixed_error(
+        &self,
+        expected: &HashMap<K, A, S>,
+        abs_tol: &D,
+        rel_tol: &D,
+    ) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &HashMap<K, A, S>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.mixed_visit(b, abs_tol, rel_tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D, S> TolError<HashMap<K, A, S>, D> for HashMap<K, B, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    B: TolError<A, D>,
+    S: BuildHasher,
+{
+    fn tol_error(&self, expected: &HashMap<K, A, S>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &HashMap<K, A, S>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.tol_visit(b, tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D> AbsError<BTreeMap<K, A>, D> for BTreeMap<K, B>
+where
+    K: Ord + fmt::Debug,
+    B: AbsError<A, D>,
+    D: PartialOrd,
+{
+    fn abs_error(&self, expected: &BTreeMap<K, A>) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+    }
+
+    fn abs_visit(
+        &self,
+        expected: &BTreeMap<K, A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.abs_visit(b, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D> RelError<BTreeMap<K, A>, D> for BTreeMap<K, B>
+where
+    K: Ord + fmt::Debug,
+    B: RelError<A, D>,
+    D: PartialOrd,
+{
+    fn rel_error(&self, expected: &BTreeMap<K, A>) -> ApproEqResult<D> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &BTreeMap<K, A>, def: RelErrorDef) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &BTreeMap<K, A>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.rel_visit(b, def, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D> UlpsError<BTreeMap<K, A>, D> for BTreeMap<K, B>
+where
+    K: Ord + fmt::Debug,
+    B: UlpsError<A, D>,
+    D: PartialOrd,
+{
+    fn ulps_error(&self, expected: &BTreeMap<K, A>) -> ApproEqResult<D> {
+        reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &BTreeMap<K, A>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.ulps_visit(b, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D> MixedError<BTreeMap<K, A>, D> for BTreeMap<K, B>
+where
+    K: Ord + fmt::Debug,
+    B: MixedError<A, D>,
+{
+    fn mixed_error(
+        &self,
+        expected: &BTreeMap<K, A>,
+        abs_tol: &D,
+        rel_tol: &D,
+    ) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &BTreeMap<K, A>,
+        abs_tol: &D,
+        rel_tol: &D,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.mixed_visit(b, abs_tol, rel_tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<K, A, B, D> TolError<BTreeMap<K, A>, D> for BTreeMap<K, B>
+where
+    K: Ord + fmt::Debug,
+    B: TolError<A, D>,
+{
+    fn tol_error(&self, expected: &BTreeMap<K, A>, tol: &Tol<D>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &BTreeMap<K, A>,
+        tol: &Tol<D>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        by_key(self, expected, path, |a, b, path| {
+            a.tol_visit(b, tol, path, visitor)
+        })
+    }
+}
@@ -3,70 +3,266 @@
 //! version 2.0 (the "License"). You can obtain a copy of the License at
 //! http://mozilla.org/MPL/2.0/.
 
+use crate::elementwise::{abs_ratio, weighted_ratio};
+use crate::report::reduce;
+use crate::visit::{each, visit, Path, Visitor};
 use crate::AbsError;
 use crate::ApproEqError;
 use crate::ApproEqResult;
+use crate::MixedError;
 use crate::RelError;
-use ndarray::{ArrayBase, Axis, Data, Dimension};
-
-fn max<D: PartialOrd, T: Iterator<Item = ApproEqResult<D>>>(iter: T) -> ApproEqResult<D> {
-    iter.fold(Ok(None), move |m, i| {
-        if match (&m, &i) {
-            (&Err(_), _) => false,
-            (_, &Err(_)) => true,
-            (&Ok(ref m), &Ok(ref i)) => match (m, i) {
-                (&None, _) => true,
-                (_, &None) => false,
-                (&Some(ref m), &Some(ref i)) => i > m,
-            },
-        } {
-            i
-        } else {
-            m
+use crate::RelErrorDef;
+use crate::Segment;
+use crate::Tol;
+use crate::TolError;
+use crate::TolsError;
+use crate::UlpsError;
+use crate::Weighted;
+use ndarray::{ArrayBase, ArrayView, Data, Dimension, IxDyn};
+
+/// Maps the position of an element in the iteration order of an array of `shape` to its coordinate.
+fn coord(shape: &[usize]) -> impl Fn(usize) -> Segment + '_ {
+    move |mut i| {
+        let mut coord = vec![0; shape.len()];
+        for (c, n) in coord.iter_mut().zip(shape).rev() {
+            *c = i % n;
+            i /= n;
         }
-    })
+        Segment::Coord(coord)
+    }
+}
+
+/// Views `expected` broadcast to the shape of `actual`, as by NumPy: the shapes are aligned on their last axes,
+/// and an axis of length 1, or missing from `expected`, stretches to the length of the axis of `actual`.
+/// Fails with `ApproEqError::BroadcastMismatch` if the shape of `expected` cannot be broadcast.
+fn broadcast<'a, A: Data, C: Data, D: Dimension, E: Dimension>(
+    actual: &ArrayBase<C, D>,
+    expected: &'a ArrayBase<A, E>,
+) -> Result<ArrayView<'a, A::Elem, D>, ApproEqError> {
+    expected
+        .broadcast(actual.raw_dim())
+        .ok_or_else(|| ApproEqError::BroadcastMismatch {
+            actual: actual.shape().to_vec(),
+            expected: expected.shape().to_vec(),
+        })
+}
+
+/// Views the tolerances of the elements broadcast to the `shape` of the values,
+/// or fails with `ApproEqError::ShapeMismatch` if they cannot be.
+fn stretch<'a, T: Data, F: Dimension>(
+    tols: &'a ArrayBase<T, F>,
+    shape: &[usize],
+) -> Result<ArrayView<'a, T::Elem, IxDyn>, ApproEqError> {
+    tols.broadcast(IxDyn(shape))
+        .ok_or_else(|| ApproEqError::ShapeMismatch {
+            values: shape.to_vec(),
+            tols: tols.shape().to_vec(),
+        })
+}
+
+/// Visits the pairs of elements of `actual` and `expected` broadcast to its shape with `element`.
+fn elements<A, C, D, E, F>(
+    actual: &ArrayBase<C, D>,
+    expected: &ArrayBase<A, E>,
+    path: &Path<'_>,
+    mut element: F,
+) -> Result<(), ApproEqError>
+where
+    A: Data,
+    C: Data,
+    D: Dimension,
+    E: Dimension,
+    F: FnMut(&C::Elem, &A::Elem, &Path<'_>) -> Result<(), ApproEqError>,
+{
+    let expected = broadcast(actual, expected).map_err(|e| path.locate(e))?;
+    each(
+        actual.iter().zip(expected.iter()),
+        path,
+        coord(actual.shape()),
+        |(i, j), path| element(i, j, path),
+    )
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
-impl<A: Data, B: PartialOrd, C: Data, D: Dimension> AbsError<ArrayBase<A, D>, B> for ArrayBase<C, D>
+impl<A: Data, B: PartialOrd, C: Data, D: Dimension, E: Dimension> AbsError<ArrayBase<A, E>, B>
+    for ArrayBase<C, D>
 where
     C::Elem: AbsError<A::Elem, B> + Sized,
 {
-    fn abs_error(&self, expected: &ArrayBase<A, D>) -> ApproEqResult<B> {
-        if self.ndim() != expected.ndim() {
-            return Err(ApproEqError::LengthMismatch);
-        }
-        for n in 0..self.ndim() {
-            if self.len_of(Axis(n)) != expected.len_of(Axis(n)) {
-                return Err(ApproEqError::LengthMismatch);
-            }
-        }
+    fn abs_error(&self, expected: &ArrayBase<A, E>) -> ApproEqResult<B> {
+        reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+    }
 
-        max(self
-            .iter()
-            .zip(expected.iter())
-            .map(move |(i, j)| i.abs_error(j)))
+    fn abs_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<B>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, path, |i, j, path| {
+            i.abs_visit(j, path, visitor)
+        })
     }
 }
 
 #[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
-impl<A: Data, B: PartialOrd, C: Data, D: Dimension> RelError<ArrayBase<A, D>, B> for ArrayBase<C, D>
+impl<A: Data, B: PartialOrd, C: Data, D: Dimension, E: Dimension> RelError<ArrayBase<A, E>, B>
+    for ArrayBase<C, D>
 where
     C::Elem: RelError<A::Elem, B> + Sized,
 {
-    fn rel_error(&self, expected: &ArrayBase<A, D>) -> ApproEqResult<B> {
-        if self.ndim() != expected.ndim() {
-            return Err(ApproEqError::LengthMismatch);
-        }
-        for n in 0..self.ndim() {
-            if self.len_of(Axis(n)) != expected.len_of(Axis(n)) {
-                return Err(ApproEqError::LengthMismatch);
-            }
-        }
+    fn rel_error(&self, expected: &ArrayBase<A, E>) -> ApproEqResult<B> {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &ArrayBase<A, E>, def: RelErrorDef) -> ApproEqResult<B> {
+        reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+    }
+
+    fn rel_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        def: RelErrorDef,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<B>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, path, |i, j, path| {
+            i.rel_visit(j, def, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
+impl<A: Data, B: PartialOrd, C: Data, D: Dimension, E: Dimension> UlpsError<ArrayBase<A, E>, B>
+    for ArrayBase<C, D>
+where
+    C::Elem: UlpsError<A::Elem, B> + Sized,
+{
+    fn ulps_error(&self, expected: &ArrayBase<A, E>) -> ApproEqResult<B> {
+        reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+    }
+
+    fn ulps_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<B>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, path, |i, j, path| {
+            i.ulps_visit(j, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
+impl<A: Data, B, C: Data, D: Dimension, E: Dimension> MixedError<ArrayBase<A, E>, B>
+    for ArrayBase<C, D>
+where
+    C::Elem: MixedError<A::Elem, B> + Sized,
+{
+    fn mixed_error(
+        &self,
+        expected: &ArrayBase<A, E>,
+        abs_tol: &B,
+        rel_tol: &B,
+    ) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+    }
+
+    fn mixed_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        abs_tol: &B,
+        rel_tol: &B,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, path, |i, j, path| {
+            i.mixed_visit(j, abs_tol, rel_tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
+impl<A: Data, B, C: Data, D: Dimension, E: Dimension> TolError<ArrayBase<A, E>, B>
+    for ArrayBase<C, D>
+where
+    C::Elem: TolError<A::Elem, B> + Sized,
+{
+    fn tol_error(&self, expected: &ArrayBase<A, E>, tol: &Tol<B>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+    }
+
+    fn tol_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        tol: &Tol<B>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        elements(self, expected, path, |i, j, path| {
+            i.tol_visit(j, tol, path, visitor)
+        })
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
+impl<A: Data, B: Data, C: Data, D: Dimension, E: Dimension, F: Dimension>
+    TolsError<ArrayBase<A, E>, ArrayBase<B, F>> for ArrayBase<C, D>
+where
+    B::Elem: Clone,
+    C::Elem: TolError<A::Elem, B::Elem> + Sized,
+{
+    fn tols_error(&self, expected: &ArrayBase<A, E>, tols: &ArrayBase<B, F>) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tols_visit(expected, tols, path, visitor))
+    }
+
+    fn tols_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        tols: &ArrayBase<B, F>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let expected = broadcast(self, expected).map_err(|e| path.locate(e))?;
+        let tols = stretch(tols, self.shape()).map_err(|e| path.locate(e))?;
+        each(
+            self.iter().zip(expected.iter()).zip(tols.iter()),
+            path,
+            coord(self.shape()),
+            |((i, j), tol), path| visit(abs_ratio(i, j, tol), path, visitor),
+        )
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.1.0"))]
+impl<A: Data, B, C: Data, D: Dimension, E: Dimension, F: Dimension, W: Data<Elem = f64>>
+    TolsError<ArrayBase<A, E>, Weighted<B, ArrayBase<W, F>>> for ArrayBase<C, D>
+where
+    C::Elem: TolError<A::Elem, B> + Sized,
+{
+    fn tols_error(
+        &self,
+        expected: &ArrayBase<A, E>,
+        tols: &Weighted<B, ArrayBase<W, F>>,
+    ) -> ApproEqResult<f64> {
+        reduce(|path, visitor| self.tols_visit(expected, tols, path, visitor))
+    }
 
-        max(self
-            .iter()
-            .zip(expected.iter())
-            .map(move |(i, j)| i.rel_error(j)))
+    fn tols_visit(
+        &self,
+        expected: &ArrayBase<A, E>,
+        tols: &Weighted<B, ArrayBase<W, F>>,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<f64>,
+    ) -> Result<(), ApproEqError> {
+        let expected = broadcast(self, expected).map_err(|e| path.locate(e))?;
+        let weights = stretch(&tols.weights, self.shape()).map_err(|e| path.locate(e))?;
+        each(
+            self.iter().zip(expected.iter()).zip(weights.iter()),
+            path,
+            coord(self.shape()),
+            |((i, j), weight), path| visit(weighted_ratio(i, j, &tols.tol, *weight), path, visitor),
+        )
     }
 }
@@ -0,0 +1,397 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::report::reduce;
+use crate::visit::{Collect, Path, Visitor};
+use crate::AbsError;
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::MixedError;
+use crate::RelError;
+use crate::RelErrorDef;
+use crate::Segment;
+use crate::Tol;
+use crate::TolError;
+use crate::TolsError;
+use crate::UlpsError;
+use std::fmt;
+use std::time::Duration;
+
+/// Implements the error traits for an adaptor of the value compared, whose `$field` passes the visitor
+/// the errors of the elements of `value` with its own `visit`.
+macro_rules! adaptor_impls {
+    ($Adaptor:ident, $field:ident) => {
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, D: Magnitude, T: AbsError<A, D> + ?Sized> AbsError<A, D>
+            for $Adaptor<'_, T>
+        {
+            fn abs_error(&self, expected: &A) -> ApproEqResult<D> {
+                reduce(|path, visitor| self.abs_visit(expected, path, visitor))
+            }
+
+            fn abs_visit(
+                &self,
+                expected: &A,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<D>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| self.value.abs_visit(expected, path, visitor),
+                    path,
+                    visitor,
+                )
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, D: Magnitude, T: RelError<A, D> + ?Sized> RelError<A, D>
+            for $Adaptor<'_, T>
+        {
+            fn rel_error(&self, expected: &A) -> ApproEqResult<D> {
+                self.rel_error_with_def(expected, RelErrorDef::default())
+            }
+
+            fn rel_error_with_def(&self, expected: &A, def: RelErrorDef) -> ApproEqResult<D> {
+                reduce(|path, visitor| self.rel_visit(expected, def, path, visitor))
+            }
+
+            fn rel_visit(
+                &self,
+                expected: &A,
+                def: RelErrorDef,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<D>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| self.value.rel_visit(expected, def, path, visitor),
+                    path,
+                    visitor,
+                )
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, D: Magnitude, T: UlpsError<A, D> + ?Sized> UlpsError<A, D>
+            for $Adaptor<'_, T>
+        {
+            fn ulps_error(&self, expected: &A) -> ApproEqResult<D> {
+                reduce(|path, visitor| self.ulps_visit(expected, path, visitor))
+            }
+
+            fn ulps_visit(
+                &self,
+                expected: &A,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<D>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| self.value.ulps_visit(expected, path, visitor),
+                    path,
+                    visitor,
+                )
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, D, T: MixedError<A, D> + ?Sized> MixedError<A, D> for $Adaptor<'_, T> {
+            fn mixed_error(&self, expected: &A, abs_tol: &D, rel_tol: &D) -> ApproEqResult<f64> {
+                reduce(|path, visitor| self.mixed_visit(expected, abs_tol, rel_tol, path, visitor))
+            }
+
+            fn mixed_visit(
+                &self,
+                expected: &A,
+                abs_tol: &D,
+                rel_tol: &D,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<f64>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| {
+                        self.value
+                            .mixed_visit(expected, abs_tol, rel_tol, path, visitor)
+                    },
+                    path,
+                    visitor,
+                )
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, D, T: TolError<A, D> + ?Sized> TolError<A, D> for $Adaptor<'_, T> {
+            fn tol_error(&self, expected: &A, tol: &Tol<D>) -> ApproEqResult<f64> {
+                reduce(|path, visitor| self.tol_visit(expected, tol, path, visitor))
+            }
+
+            fn tol_visit(
+                &self,
+                expected: &A,
+                tol: &Tol<D>,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<f64>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| self.value.tol_visit(expected, tol, path, visitor),
+                    path,
+                    visitor,
+                )
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl<A: ?Sized, U: ?Sized, T: TolsError<A, U> + ?Sized> TolsError<A, U>
+            for $Adaptor<'_, T>
+        {
+            fn tols_error(&self, expected: &A, tols: &U) -> ApproEqResult<f64> {
+                reduce(|path, visitor| self.tols_visit(expected, tols, path, visitor))
+            }
+
+            fn tols_visit(
+                &self,
+                expected: &A,
+                tols: &U,
+                path: &Path<'_>,
+                visitor: &mut dyn Visitor<f64>,
+            ) -> Result<(), ApproEqError> {
+                self.$field.visit(
+                    |path, visitor| self.value.tols_visit(expected, tols, path, visitor),
+                    path,
+                    visitor,
+                )
+            }
+        }
+    };
+}
+
+/// Norm reducing the errors of the elements of a collection to the error of the whole.
+///
+/// Collections reduce the errors of their elements by `Norm::Max`. `Norm::of` applies another norm
+/// to the slices, `Vec`, arrays, `VecDeque`, maps and `ndarray::ArrayBase` held by a value.
+/// The elements of nested collections, tuples and derived structs are reduced together,
+/// as one collection of all the elements they hold.
+///
+/// The norms other than `Norm::Max` compute with the errors as `f64`, so they require errors
+/// that implement `Magnitude`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::Norm;
+/// # fn main() {
+/// let expected = vec![1f64, 2.0, 3.0, 4.0];
+/// let actual = vec![1.1f64, 2.0, 3.0, 4.0];
+/// assert_appro_eq!(actual, expected, 0.06, norm = Norm::Rms); // does not panic
+/// assert_appro_ne!(actual, expected, 0.06); // does not panic
+/// assert_appro_eq!(Norm::Rms.of(&actual), expected, 0.06); // does not panic
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::{AbsError, ApproEqError, Norm};
+/// #[derive(Clone, Debug, PartialEq, PartialOrd)]
+/// struct Level(u8);
+///
+/// impl AbsError<Level, Level> for Level {
+///     fn abs_error(&self, expected: &Level) -> Result<Option<Level>, ApproEqError> {
+///         Ok(Some(Level(self.0.abs_diff(expected.0))))
+///     }
+/// }
+///
+/// # fn main() {
+/// // `Level` does not implement `Magnitude`, so its errors have no mean.
+/// assert_appro_eq!(vec![Level(1)], vec![Level(2)], Level(1), norm = Norm::Mean);
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Norm {
+    /// The largest error, the L-infinity norm.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Max,
+    /// The mean of the absolute errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Mean,
+    /// The root mean square of the errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Rms,
+    /// The sum of the absolute errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    L1,
+    /// The Euclidean norm, the square root of the sum of the squared errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    L2,
+    /// The given percentile, from 0 to 100, of the errors by the nearest rank.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Percentile(#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))] f64),
+}
+
+/// The default norm is `Norm::Max`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Default for Norm {
+    fn default() -> Norm {
+        Norm::Max
+    }
+}
+
+impl Norm {
+    /// Reduces the errors of the elements of `value` by this norm, when it is compared.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn of<T: ?Sized>(self, value: &T) -> Normed<'_, T> {
+        Normed { norm: self, value }
+    }
+
+    /// Passes `visitor` the errors visited by `elements` at `path`, reduced by this norm.
+    ///
+    /// `Norm::Max` passes on the errors of all the elements, which the visitor reduces itself.
+    /// The other norms pass the error of the whole at the path to the element it is the error of,
+    /// or at `path` if it is not the error of one.
+    fn visit<D, F>(
+        self,
+        elements: F,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError>
+    where
+        D: Magnitude,
+        F: FnOnce(&Path<'_>, &mut dyn Visitor<D>) -> Result<(), ApproEqError>,
+    {
+        if self == Norm::Max {
+            return elements(path, visitor);
+        }
+        let mut errors = Collect {
+            paths: matches!(self, Norm::Max | Norm::Percentile(_)),
+            errors: Vec::new(),
+        };
+        elements(&Path::ROOT, &mut errors).map_err(|e| path.locate(e))?;
+        if let Some((error, segments)) = self.reduce(errors.errors)? {
+            path.visit_at(&segments, error, visitor);
+        }
+        Ok(())
+    }
+
+    /// Reduces `errors` by this norm.
+    fn reduce<D: Magnitude>(
+        self,
+        mut errors: Vec<(D, Vec<Segment>)>,
+    ) -> Result<Option<(D, Vec<Segment>)>, ApproEqError> {
+        if errors.is_empty() {
+            return Ok(None);
+        }
+        let n = errors.len() as f64;
+        let abs = || errors.iter().map(|(error, _)| error.to_f64().abs());
+        let value = match self {
+            Norm::Max => {
+                let max = errors
+                    .into_iter()
+                    .max_by(|(a, _), (b, _)| a.to_f64().total_cmp(&b.to_f64()));
+                return Ok(max);
+            }
+            Norm::Percentile(percentile) => {
+                errors.sort_by(|(a, _), (b, _)| a.to_f64().total_cmp(&b.to_f64()));
+                let rank = Norm::rank(percentile, errors.len());
+                return Ok(Some(errors.swap_remove(rank - 1)));
+            }
+            Norm::Mean => abs().sum::<f64>() / n,
+            Norm::Rms => (abs().map(|error| error * error).sum::<f64>() / n).sqrt(),
+            Norm::L1 => abs().sum(),
+            Norm::L2 => abs().map(|error| error * error).sum::<f64>().sqrt(),
+        };
+        D::from_f64(value).map(|error| Some((error, Vec::new())))
+    }
+
+    /// The 1-based rank of the error selected by `Norm::Percentile` among `n` errors sorted in ascending order.
+    pub(crate) fn rank(percentile: f64, n: usize) -> usize {
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * n as f64).ceil();
+        if rank.is_nan() {
+            n
+        } else {
+            (rank as usize).clamp(1, n)
+        }
+    }
+}
+
+/// Errors with a magnitude as `f64`, which the norms other than `Norm::Max` compute with.
+///
+/// It is implemented for the floating point and integer types and for `Duration`, in seconds.
+/// Implement it for the error type of other impls to reduce their errors by those norms.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub trait Magnitude: PartialOrd + Sized {
+    /// The magnitude of the error as `f64`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn to_f64(&self) -> f64;
+
+    /// The error of magnitude `value`, or `ApproEqError::Overflow` if the type cannot represent it.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    fn from_f64(value: f64) -> Result<Self, ApproEqError>;
+}
+
+macro_rules! float_magnitude_impls {
+    ($($T:ty)*) => ($(
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl Magnitude for $T {
+            fn to_f64(&self) -> f64 {
+                *self as f64
+            }
+
+            fn from_f64(value: f64) -> Result<$T, ApproEqError> {
+                Ok(value as $T)
+            }
+        }
+    )*)
+}
+
+float_magnitude_impls! { f32 f64 }
+
+macro_rules! int_magnitude_impls {
+    ($($T:ty)*) => ($(
+        /// Norms are rounded to the nearest integer.
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl Magnitude for $T {
+            fn to_f64(&self) -> f64 {
+                *self as f64
+            }
+
+            fn from_f64(value: f64) -> Result<$T, ApproEqError> {
+                let value = value.round();
+                if value < <$T>::MIN as f64 || value > <$T>::MAX as f64 {
+                    return Err(ApproEqError::Overflow);
+                }
+                Ok(value as $T)
+            }
+        }
+    )*)
+}
+
+int_magnitude_impls! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Magnitude for Duration {
+    fn to_f64(&self) -> f64 {
+        self.as_secs_f64()
+    }
+
+    fn from_f64(value: f64) -> Result<Duration, ApproEqError> {
+        Duration::try_from_secs_f64(value).map_err(|_| ApproEqError::Overflow)
+    }
+}
+
+/// A value whose errors are reduced by a norm when it is compared, made by `Norm::of`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub struct Normed<'a, T: ?Sized> {
+    norm: Norm,
+    value: &'a T,
+}
+
+/// Formats the value the norm applies to.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Normed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+adaptor_impls! { Normed, norm }
@@ -0,0 +1,200 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::report::reduce;
+use crate::visit::{Collect, Path, Visitor};
+use crate::AbsError;
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::Magnitude;
+use crate::MixedError;
+use crate::RelError;
+use crate::RelErrorDef;
+use crate::Tol;
+use crate::TolError;
+use crate::TolsError;
+use crate::UlpsError;
+use std::fmt;
+
+/// Number of elements of a collection allowed to exceed the tolerance.
+///
+/// Allowed among the elements of a value by `Outliers::of`, they let a comparison pass when at most `count`
+/// elements, or `fraction` of the elements if more, exceed the tolerance, and none exceeds `cap`.
+/// The paths to the elements exceeding the tolerance are listed in `Report::outliers`, or by the cause
+/// `ApproEqError::TooManyOutliers` of the `Mismatch`.
+/// The elements are the values in the innermost slices, `Vec`, arrays, `VecDeque`, maps, `ndarray::ArrayBase`,
+/// tuples and derived structs, and values that are not collections are compared as usual.
+///
+/// The error of the value is the largest error of its elements once the allowed outliers are left out,
+/// or `ApproEqError::OutlierCap` if an element exceeds `cap`. The errors are compared with `cap` as `f64`,
+/// so outliers require errors that implement `Magnitude`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::Outliers;
+/// # fn main() {
+/// let expected = vec![1f64; 1000];
+/// let mut actual = expected.clone();
+/// actual[17] = 1.01;
+/// assert_appro_eq!(actual, expected, 1e-6, outliers = Outliers::count(1).cap(0.1)); // does not panic
+/// assert_appro_eq!(actual, expected, 1e-6, outliers = Outliers::fraction(0.001)); // does not panic
+/// assert_appro_eq!(Outliers::count(1).of(&actual), expected, 1e-6); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Outliers {
+    /// The number of elements allowed to exceed the tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub count: usize,
+    /// The fraction of the elements allowed to exceed the tolerance, rounded down.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fraction: f64,
+    /// The error no element may exceed, as `f64`, in seconds for `Duration`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub cap: Option<f64>,
+}
+
+impl Outliers {
+    /// Allows `count` elements to exceed the tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn count(count: usize) -> Outliers {
+        Outliers {
+            count,
+            ..Outliers::default()
+        }
+    }
+
+    /// Allows `fraction` of the elements to exceed the tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn fraction(fraction: f64) -> Outliers {
+        Outliers {
+            fraction,
+            ..Outliers::default()
+        }
+    }
+
+    /// Sets the error no element may exceed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn cap(mut self, cap: f64) -> Outliers {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Allows these outliers among the elements of `value`, when it is compared.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn of<T: ?Sized>(self, value: &T) -> WithOutliers<'_, T> {
+        WithOutliers {
+            outliers: self,
+            value,
+        }
+    }
+
+    /// Passes `visitor` the errors visited by `elements` at `path`, with these outliers allowed.
+    ///
+    /// A visitor that counts the outliers itself is passed the errors of all the elements,
+    /// and told of those exceeding the cap. Others are passed the largest error once the outliers
+    /// are left out, at the path to its element.
+    fn visit<D, F>(
+        self,
+        elements: F,
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+    ) -> Result<(), ApproEqError>
+    where
+        D: Magnitude,
+        F: FnOnce(&Path<'_>, &mut dyn Visitor<D>) -> Result<(), ApproEqError>,
+    {
+        if visitor.allow(&self) {
+            return elements(
+                path,
+                &mut Capped {
+                    cap: self.cap,
+                    visitor,
+                },
+            );
+        }
+        let mut errors = Collect {
+            paths: true,
+            errors: Vec::new(),
+        };
+        elements(&Path::ROOT, &mut errors).map_err(|e| path.locate(e))?;
+        let mut errors = errors.errors;
+        if let [(_, segments)] = &errors[..] {
+            if segments.is_empty() {
+                let (error, _) = errors.swap_remove(0);
+                visitor.visit(path, error);
+                return Ok(());
+            }
+        }
+        if let Some(cap) = self.cap {
+            let over = errors
+                .iter()
+                .position(|(error, _)| error.to_f64().abs() > cap);
+            if let Some(i) = over {
+                let (_, segments) = errors.swap_remove(i);
+                return Err(path.locate_at(segments, ApproEqError::OutlierCap(cap)));
+            }
+        }
+        let allowed = self.allowed(errors.len());
+        errors.sort_by(|(a, _), (b, _)| b.to_f64().total_cmp(&a.to_f64()));
+        if let Some((error, segments)) = errors.into_iter().nth(allowed) {
+            path.visit_at(&segments, error, visitor);
+        }
+        Ok(())
+    }
+
+    /// The number of elements allowed to exceed the tolerance out of `total`.
+    pub(crate) fn allowed(&self, total: usize) -> usize {
+        let fraction = (self.fraction * total as f64).floor();
+        if fraction > 0.0 {
+            self.count.max(fraction as usize)
+        } else {
+            self.count
+        }
+    }
+}
+
+/// Tells the visitor it wraps of the elements whose error exceeds `cap`.
+struct Capped<'v, D> {
+    cap: Option<f64>,
+    visitor: &'v mut dyn Visitor<D>,
+}
+
+impl<D: Magnitude> Visitor<D> for Capped<'_, D> {
+    fn visit(&mut self, path: &Path<'_>, error: D) {
+        if self.cap.is_some_and(|cap| error.to_f64().abs() > cap) {
+            self.visitor.over_cap(path);
+        }
+        self.visitor.visit(path, error);
+    }
+
+    fn allow(&mut self, outliers: &Outliers) -> bool {
+        self.visitor.allow(outliers)
+    }
+
+    fn over_cap(&mut self, path: &Path<'_>) {
+        self.visitor.over_cap(path);
+    }
+}
+
+/// A value with outliers allowed among its elements when it is compared, made by `Outliers::of`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub struct WithOutliers<'a, T: ?Sized> {
+    outliers: Outliers,
+    value: &'a T,
+}
+
+/// Formats the value the outliers are allowed in.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<T: fmt::Debug + ?Sized> fmt::Debug for WithOutliers<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+adaptor_impls! { WithOutliers, outliers }
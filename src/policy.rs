@@ -0,0 +1,110 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use std::cell::Cell;
+
+thread_local!(static CURRENT: Cell<FloatPolicy> = Cell::new(FloatPolicy::default()));
+
+/// Policy for comparing NaN, infinities and signed zeros of `f32` and `f64`.
+///
+/// The policy in effect is local to the current thread. It is set for the duration of a closure
+/// by `FloatPolicy::scope`, and reaches every comparison of floating point values made inside it,
+/// including the elements of slices, `Vec`, arrays and `ndarray::ArrayBase`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::FloatPolicy;
+/// # fn main() {
+/// let policy = FloatPolicy { nan_eq: true, ..FloatPolicy::default() };
+/// assert_appro_eq!(vec![1f64, f64::NAN], vec![1f64, f64::NAN], policy = policy); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FloatPolicy {
+    /// NaN compares equal to NaN. Otherwise any NaN results in `ApproEqError::NonNumDifference`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub nan_eq: bool,
+    /// Infinities of the same sign compare equal. Otherwise they result in `ApproEqError::NonNumDifference`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub inf_eq: bool,
+    /// -0.0 and +0.0 are distinguished, resulting in `ApproEqError::SignMismatch`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub signed_zero: bool,
+}
+
+/// The default policy fails on NaN and on infinities, even of the same sign, and ignores the sign of zero.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Default for FloatPolicy {
+    fn default() -> FloatPolicy {
+        FloatPolicy {
+            nan_eq: false,
+            inf_eq: false,
+            signed_zero: false,
+        }
+    }
+}
+
+struct Restore(FloatPolicy);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.0));
+    }
+}
+
+impl FloatPolicy {
+    /// Returns the policy in effect on the current thread.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn current() -> FloatPolicy {
+        CURRENT.with(|current| current.get())
+    }
+
+    /// Calls `f` with this policy in effect on the current thread.
+    ///
+    /// The previous policy is restored when `f` returns or panics.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn scope<R, F: FnOnce() -> R>(self, f: F) -> R {
+        let _restore = Restore(CURRENT.with(|current| current.replace(self)));
+        f()
+    }
+
+    /// Measures the error between two floating point values with `error`, unless either of them
+    /// is a special value under the current policy, in which case they are either equal(`zero`)
+    /// or an error is returned.
+    pub(crate) fn measure<D, F: FnOnce() -> ApproEqResult<D>>(
+        actual: f64,
+        expected: f64,
+        zero: D,
+        error: F,
+    ) -> ApproEqResult<D> {
+        let policy = FloatPolicy::current();
+        if actual.is_nan() || expected.is_nan() {
+            if policy.nan_eq && actual.is_nan() && expected.is_nan() {
+                Ok(Some(zero))
+            } else {
+                Err(ApproEqError::NonNumDifference)
+            }
+        } else if actual.is_infinite() && actual == expected {
+            if policy.inf_eq {
+                Ok(Some(zero))
+            } else {
+                Err(ApproEqError::NonNumDifference)
+            }
+        } else if policy.signed_zero
+            && actual == 0.0
+            && expected == 0.0
+            && actual.is_sign_negative() != expected.is_sign_negative()
+        {
+            Err(ApproEqError::SignMismatch)
+        } else {
+            error()
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::AbsError;
 use crate::ApproEqError;
 use crate::ApproEqResult;
 use crate::RelError;
+use crate::RelErrorDef;
 use crate::Tolerance;
 use num_integer::Integer;
 use num_rational::Ratio;
@@ -33,7 +34,21 @@ impl<A: Integer + Clone> AbsError<Ratio<A>, Ratio<A>> for Ratio<A> {
 #[cfg_attr(feature = "docs", stable(feature = "num-rational", since = "0.1.0"))]
 impl<A: Integer + Clone> RelError<Ratio<A>, Ratio<A>> for Ratio<A> {
     fn rel_error(&self, expected: &Ratio<A>) -> ApproEqResult<Ratio<A>> {
-        if *expected == Ratio::zero() {
+        self.rel_error_with_def(expected, RelErrorDef::default())
+    }
+
+    fn rel_error_with_def(&self, expected: &Ratio<A>, def: RelErrorDef) -> ApproEqResult<Ratio<A>> {
+        let magnitude = |x: &Ratio<A>| {
+            if *x < Ratio::zero() {
+                Ratio::zero() - x
+            } else {
+                x.clone()
+            }
+        };
+        let base = def.base(magnitude(self), magnitude(expected), |a, b| {
+            (a + b) / Ratio::from_integer(A::one() + A::one())
+        });
+        if base == Ratio::zero() {
             Err(ApproEqError::DividedByZero)
         } else {
             Ok(Some(
@@ -41,7 +56,7 @@ impl<A: Integer + Clone> RelError<Ratio<A>, Ratio<A>> for Ratio<A> {
                     self - expected
                 } else {
                     expected - self
-                }) / expected,
+                }) / base,
             ))
         }
     }
@@ -0,0 +1,709 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::visit::{Path, Visitor};
+use crate::AbsError;
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::MixedError;
+use crate::Outliers;
+use crate::RelError;
+use crate::RelErrorDef;
+use crate::Tol;
+use crate::TolError;
+use crate::TolsError;
+use crate::UlpsError;
+use std::error;
+use std::fmt;
+
+/// A step from a collection to one of its elements.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// The index of an element of a slice, `Vec` or array.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Index(usize),
+    /// The coordinate of an element of `ndarray::ArrayBase`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Coord(Vec<usize>),
+    /// The name of a field of a struct or enum variant, or the position of an element of a tuple or tuple struct.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Field(&'static str),
+    /// The key of an entry of a map, in `Debug` format.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    Key(String),
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Index(i) => write!(f, "[{}]", i),
+            Segment::Coord(coord) => {
+                write!(f, "[")?;
+                for (n, i) in coord.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", i)?;
+                }
+                write!(f, "]")
+            }
+            Segment::Field(name) => write!(f, ".{}", name),
+            Segment::Key(key) => write!(f, "[{}]", key),
+        }
+    }
+}
+
+/// The result of a comparison that passed.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report<E, T = E> {
+    /// The largest error measured, or `None` if there was nothing to measure.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub error: Option<E>,
+    /// The tolerance compared with.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub tol: T,
+    /// The path to the element with the largest error, empty if the values are not collections.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub path: Vec<Segment>,
+    /// The paths to the elements exceeding the tolerance when `Outliers` are allowed, empty otherwise.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub outliers: Vec<Vec<Segment>>,
+}
+
+/// The result of a comparison that failed.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Debug)]
+pub struct Mismatch<E, T = E> {
+    /// The largest error measured, or `None` if the error could not be measured.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub error: Option<E>,
+    /// The tolerance compared with.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub tol: T,
+    /// The path to the element with the largest error, or to the element the error could not be measured for.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub path: Vec<Segment>,
+    /// Why the error could not be measured.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub cause: Option<ApproEqError>,
+    /// The number of elements whose error exceeds the tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub failing: usize,
+}
+
+impl<E: fmt::Debug, T: fmt::Debug> Mismatch<E, T> {
+    fn describe(&self, f: &mut fmt::Formatter<'_>, default: bool) -> fmt::Result {
+        match (&self.cause, &self.error) {
+            (Some(cause), Some(error)) => write!(f, "{}, error `{:?}`", cause, error)?,
+            (Some(cause), None) => write!(f, "{}", cause)?,
+            (None, Some(error)) => write!(
+                f,
+                "error `{:?}` exceeds {}tolerance `{:?}`",
+                error,
+                if default { "default " } else { "" },
+                self.tol
+            )?,
+            (None, None) => write!(f, "not approximately equal")?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " at ")?;
+            for segment in &self.path {
+                write!(f, "{}", segment)?;
+            }
+        }
+        if self.failing > 1 && !matches!(self.cause, Some(ApproEqError::TooManyOutliers { .. })) {
+            write!(f, " ({} elements exceed the tolerance)", self.failing)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<E: fmt::Debug, T: fmt::Debug> fmt::Display for Mismatch<E, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.describe(f, false)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<E: fmt::Debug, T: fmt::Debug> error::Error for Mismatch<E, T> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause
+            .as_ref()
+            .map(|cause| cause as &(dyn error::Error + 'static))
+    }
+}
+
+/// Keeps the largest error visited, with the path to it if `paths` is set.
+struct Largest<D> {
+    paths: bool,
+    max: Option<(D, Vec<Segment>)>,
+}
+
+impl<D: PartialOrd> Visitor<D> for Largest<D> {
+    fn visit(&mut self, path: &Path<'_>, error: D) {
+        if self.max.as_ref().map_or(true, |(max, _)| error > *max) {
+            let mut segments = self.max.take().map_or_else(Vec::new, |(_, path)| path);
+            if self.paths {
+                path.write(&mut segments);
+            }
+            self.max = Some((error, segments));
+        }
+    }
+}
+
+/// Reduces the errors of the elements visited by `visit` to the largest one.
+pub(crate) fn reduce<D, F>(visit: F) -> ApproEqResult<D>
+where
+    D: PartialOrd,
+    F: FnOnce(&Path<'_>, &mut dyn Visitor<D>) -> Result<(), ApproEqError>,
+{
+    let mut largest = Largest {
+        paths: false,
+        max: None,
+    };
+    visit(&Path::ROOT, &mut largest)?;
+    Ok(largest.max.map(|(error, _)| error))
+}
+
+/// Reduces the errors visited for a check, counting the elements exceeding the tolerance.
+struct Tally<'t, E, T, W> {
+    largest: Largest<E>,
+    within: W,
+    tol: &'t T,
+    failing: usize,
+    /// The number of elements of collections visited.
+    total: usize,
+    /// The outliers allowed, with the paths to the elements exceeding the tolerance.
+    outliers: Option<(Outliers, Vec<Vec<Segment>>)>,
+    /// The number of elements exceeding the cap of the outliers.
+    over_cap: usize,
+}
+
+impl<E, T, W> Visitor<E> for Tally<'_, E, T, W>
+where
+    E: PartialOrd,
+    W: Fn(&E, &T) -> bool,
+{
+    fn visit(&mut self, path: &Path<'_>, error: E) {
+        let exceeds = !(self.within)(&error, self.tol);
+        if exceeds {
+            self.failing += 1;
+        }
+        if !path.is_root() {
+            self.total += 1;
+            if let Some((_, paths)) = self.outliers.as_mut() {
+                if exceeds {
+                    paths.push(path.segments());
+                }
+            }
+        }
+        self.largest.visit(path, error);
+    }
+
+    fn allow(&mut self, outliers: &Outliers) -> bool {
+        if self.outliers.is_some() {
+            return false;
+        }
+        self.outliers = Some((*outliers, Vec::new()));
+        true
+    }
+
+    fn over_cap(&mut self, _: &Path<'_>) {
+        self.over_cap += 1;
+    }
+}
+
+fn check<E, T, F, W>(visit: F, within: W, tol: T) -> Result<Report<E, T>, Mismatch<E, T>>
+where
+    E: PartialOrd,
+    F: FnOnce(&Path<'_>, &mut dyn Visitor<E>) -> Result<(), ApproEqError>,
+    W: Fn(&E, &T) -> bool,
+{
+    let mut tally = Tally {
+        largest: Largest {
+            paths: true,
+            max: None,
+        },
+        within,
+        tol: &tol,
+        failing: 0,
+        total: 0,
+        outliers: None,
+        over_cap: 0,
+    };
+    let visited = visit(&Path::ROOT, &mut tally);
+    let Tally {
+        largest,
+        within,
+        failing,
+        total,
+        outliers,
+        over_cap,
+        ..
+    } = tally;
+    let (outliers, paths) = match outliers {
+        Some((outliers, paths)) if total > 0 => (Some(outliers), paths),
+        _ => (None, Vec::new()),
+    };
+    match (visited.map(|()| largest.max), outliers) {
+        (Ok(Some((error, path))), Some(outliers)) => {
+            let allowed = outliers.allowed(total);
+            let cause = if let Some(cap) = outliers.cap.filter(|_| over_cap > 0) {
+                ApproEqError::OutlierCap(cap)
+            } else if failing > allowed {
+                ApproEqError::TooManyOutliers {
+                    allowed,
+                    outliers: paths,
+                }
+            } else {
+                return Ok(Report {
+                    error: Some(error),
+                    tol,
+                    path,
+                    outliers: paths,
+                });
+            };
+            Err(Mismatch {
+                error: Some(error),
+                tol,
+                path,
+                cause: Some(cause),
+                failing,
+            })
+        }
+        (Ok(Some((error, path))), None) if !within(&error, &tol) => Err(Mismatch {
+            error: Some(error),
+            tol,
+            path,
+            cause: None,
+            failing: failing.max(1),
+        }),
+        (Ok(worst), _) => {
+            let (error, path) =
+                worst.map_or((None, Vec::new()), |(error, path)| (Some(error), path));
+            Ok(Report {
+                error,
+                tol,
+                path,
+                outliers: paths,
+            })
+        }
+        (Err(cause), _) => {
+            let (path, cause) = cause.split();
+            Err(Mismatch {
+                error: None,
+                tol,
+                path,
+                cause: Some(cause),
+                failing,
+            })
+        }
+    }
+}
+
+/// Compares the absolute error of `actual` and `expected` with `tol`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use appro_eq::{abs_appro_check, Segment};
+/// let mismatch = abs_appro_check(&vec![1f64, 2.0, 3.0], &vec![1f64, 2.5, 3.1], &0.2).unwrap_err();
+/// assert_eq!(mismatch.error, Some(0.5));
+/// assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn abs_appro_check<A: AbsError<B, D> + ?Sized, B: ?Sized, D: PartialOrd + Clone>(
+    actual: &A,
+    expected: &B,
+    tol: &D,
+) -> Result<Report<D>, Mismatch<D>> {
+    check(
+        |path, visitor| actual.abs_visit(expected, path, visitor),
+        |e, tol| e <= tol,
+        tol.clone(),
+    )
+}
+
+/// Compares the relative error of `actual` and `expected` with `tol`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn rel_appro_check<A: RelError<B, D> + ?Sized, B: ?Sized, D: PartialOrd + Clone>(
+    actual: &A,
+    expected: &B,
+    tol: &D,
+) -> Result<Report<D>, Mismatch<D>> {
+    rel_appro_check_with_def(actual, expected, tol, RelErrorDef::default())
+}
+
+/// Compares the relative error of `actual` and `expected`, as defined by `def`, with `tol`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn rel_appro_check_with_def<A: RelError<B, D> + ?Sized, B: ?Sized, D: PartialOrd + Clone>(
+    actual: &A,
+    expected: &B,
+    tol: &D,
+    def: RelErrorDef,
+) -> Result<Report<D>, Mismatch<D>> {
+    check(
+        |path, visitor| actual.rel_visit(expected, def, path, visitor),
+        |e, tol| e <= tol,
+        tol.clone(),
+    )
+}
+
+/// Compares the distance of `actual` and `expected` in units in the last place with `tol`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn ulps_appro_check<A: UlpsError<B, D> + ?Sized, B: ?Sized, D: PartialOrd + Clone>(
+    actual: &A,
+    expected: &B,
+    tol: &D,
+) -> Result<Report<D>, Mismatch<D>> {
+    check(
+        |path, visitor| actual.ulps_visit(expected, path, visitor),
+        |e, tol| e <= tol,
+        tol.clone(),
+    )
+}
+
+/// Compares `actual` and `expected` with the absolute tolerance `abs_tol` or the relative tolerance `rel_tol`.
+///
+/// The error is the ratio measured by `MixedError`, and the tolerance is reported as a `Tol`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn mixed_appro_check<A: MixedError<B, D> + ?Sized, B: ?Sized, D: Clone>(
+    actual: &A,
+    expected: &B,
+    abs_tol: &D,
+    rel_tol: &D,
+) -> Result<Report<f64, Tol<D>>, Mismatch<f64, Tol<D>>> {
+    check(
+        |path, visitor| actual.mixed_visit(expected, abs_tol, rel_tol, path, visitor),
+        |e, _| *e <= 1.0,
+        Tol::new()
+            .abs(abs_tol.clone())
+            .rel(rel_tol.clone())
+            .rel_def(RelErrorDef::Max),
+    )
+}
+
+/// Compares `actual` and `expected` with `tol`.
+///
+/// The error is the ratio measured by `TolError`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn tol_appro_check<A: TolError<B, D> + ?Sized, B: ?Sized, D: Clone>(
+    actual: &A,
+    expected: &B,
+    tol: &Tol<D>,
+) -> Result<Report<f64, Tol<D>>, Mismatch<f64, Tol<D>>> {
+    let check = || {
+        check(
+            |path, visitor| actual.tol_visit(expected, tol, path, visitor),
+            |e, _| *e <= 1.0,
+            tol.clone(),
+        )
+    };
+    match tol.policy {
+        Some(policy) => policy.scope(check),
+        None => check(),
+    }
+}
+
+/// Compares `actual` and `expected` element by element with the tolerances `tols` of the same shape.
+///
+/// The error is the ratio measured by `TolsError`, and the tolerance is 1.
+///
+/// # Examples
+///
+/// ```rust
+/// # use appro_eq::{tols_appro_check, Segment};
+/// let tols = vec![10f64, 0.01];
+/// let mismatch = tols_appro_check(&vec![101_330f64, 293.2], &vec![101_325f64, 293.15], &tols).unwrap_err();
+/// assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn tols_appro_check<A: TolsError<B, T> + ?Sized, B: ?Sized, T: ?Sized>(
+    actual: &A,
+    expected: &B,
+    tols: &T,
+) -> Result<Report<f64>, Mismatch<f64>> {
+    check(
+        |path, visitor| actual.tols_visit(expected, tols, path, visitor),
+        |e, tol| e <= tol,
+        1.0,
+    )
+}
+
+/// Support for the assertion macros.
+#[doc(hidden)]
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+pub mod support {
+    use super::{Mismatch, Report, Segment};
+    pub use crate::tol::Query;
+    use crate::visit::each;
+    pub use crate::visit::{visit, Path, Visitor};
+    use crate::{AbsError, MixedError, RelError, UlpsError};
+    use crate::{AbsTolerance, ApproEqError, ApproEqResult, RelTolerance, Tol, UlpsTolerance};
+    use std::fmt;
+
+    pub fn abs_appro_check<A, B, D>(actual: &A, expected: &B) -> Result<Report<D>, Mismatch<D>>
+    where
+        A: AbsError<B, D> + ?Sized,
+        B: ?Sized,
+        D: PartialOrd + Clone + AbsTolerance<D>,
+    {
+        super::abs_appro_check(actual, expected, &D::abs_tolerance())
+    }
+
+    /// The values compared by the relative assertion macros with the default tolerance.
+    ///
+    /// The macros call `rel_appro_check` on `&&&RelCheck(actual, expected)`, which compares
+    /// the relative error as an `f64` ratio when the values have one, against their own default
+    /// `f64` tolerance if they have one or else that of `f64`, and otherwise the relative error
+    /// of the type the values have a default tolerance for.
+    pub struct RelCheck<'a, A: ?Sized, B: ?Sized>(pub &'a A, pub &'a B);
+
+    /// Compares values with an `f64` relative error against their own default `f64` tolerance.
+    pub trait RelCheckOwn {
+        fn rel_appro_check(self) -> Result<Report<f64>, Mismatch<f64>>;
+    }
+
+    impl<A, B> RelCheckOwn for &&&RelCheck<'_, A, B>
+    where
+        A: RelError<B, f64> + RelTolerance<f64> + ?Sized,
+        B: ?Sized,
+    {
+        fn rel_appro_check(self) -> Result<Report<f64>, Mismatch<f64>> {
+            super::rel_appro_check_with_def(self.0, self.1, &A::rel_tolerance(), A::rel_error_def())
+        }
+    }
+
+    /// Compares values with an `f64` relative error against the default tolerance of `f64`.
+    pub trait RelCheckRatio {
+        fn rel_appro_check(self) -> Result<Report<f64>, Mismatch<f64>>;
+    }
+
+    impl<A, B> RelCheckRatio for &&RelCheck<'_, A, B>
+    where
+        A: RelError<B, f64> + ?Sized,
+        B: ?Sized,
+    {
+        fn rel_appro_check(self) -> Result<Report<f64>, Mismatch<f64>> {
+            super::rel_appro_check_with_def(
+                self.0,
+                self.1,
+                &f64::rel_tolerance(),
+                <f64 as RelTolerance>::rel_error_def(),
+            )
+        }
+    }
+
+    /// Compares values with a relative error of a type `D` against the default tolerance of `D`.
+    pub trait RelCheckDiff<D> {
+        fn rel_appro_check(self) -> Result<Report<D>, Mismatch<D>>;
+    }
+
+    impl<A, B, D> RelCheckDiff<D> for &RelCheck<'_, A, B>
+    where
+        A: RelError<B, D> + ?Sized,
+        B: ?Sized,
+        D: PartialOrd + Clone + RelTolerance<D>,
+    {
+        fn rel_appro_check(self) -> Result<Report<D>, Mismatch<D>> {
+            super::rel_appro_check_with_def(self.0, self.1, &D::rel_tolerance(), D::rel_error_def())
+        }
+    }
+
+    pub fn ulps_appro_check<A, B, D>(actual: &A, expected: &B) -> Result<Report<D>, Mismatch<D>>
+    where
+        A: UlpsError<B, D> + ?Sized,
+        B: ?Sized,
+        D: PartialOrd + Clone + UlpsTolerance<D>,
+    {
+        super::ulps_appro_check(actual, expected, &D::ulps_tolerance())
+    }
+
+    pub fn mixed_appro_check<A, B, D>(
+        actual: &A,
+        expected: &B,
+    ) -> Result<Report<f64, Tol<D>>, Mismatch<f64, Tol<D>>>
+    where
+        A: MixedError<B, D> + ?Sized,
+        B: ?Sized,
+        D: Clone + AbsTolerance<D> + RelTolerance<D>,
+    {
+        super::mixed_appro_check(actual, expected, &D::abs_tolerance(), &D::rel_tolerance())
+    }
+
+    /// Visits the errors of a field of a derived impl, at the path to the field.
+    pub type Field<'a, D> = &'a dyn Fn(&Path<'_>, &mut dyn Visitor<D>) -> Result<(), ApproEqError>;
+
+    /// Reduces the errors of the fields of a derived impl visited by `visit`.
+    pub fn reduce<D, F>(visit: F) -> ApproEqResult<D>
+    where
+        D: PartialOrd,
+        F: FnOnce(&Path<'_>, &mut dyn Visitor<D>) -> Result<(), ApproEqError>,
+    {
+        super::reduce(visit)
+    }
+
+    /// Visits the errors of the named fields of a derived impl, or of a tuple.
+    pub fn fields<D>(
+        path: &Path<'_>,
+        visitor: &mut dyn Visitor<D>,
+        fields: &[(&'static str, Field<'_, D>)],
+    ) -> Result<(), ApproEqError> {
+        each(
+            fields.iter(),
+            path,
+            |i| Segment::Field(fields[i].0),
+            |(_, field), path| field(path, visitor),
+        )
+    }
+
+    /// Compares a field marked `exact` of a derived impl.
+    pub fn exact<T: PartialEq + ?Sized, D>(actual: &T, expected: &T) -> ApproEqResult<D> {
+        if actual == expected {
+            Ok(None)
+        } else {
+            Err(ApproEqError::ValueMismatch)
+        }
+    }
+
+    /// Turns the check of a field with its own tolerance in a derived impl into an error.
+    pub fn field_check<E, T>(
+        check: Result<Report<E, T>, Mismatch<E, T>>,
+    ) -> Result<(), ApproEqError>
+    where
+        E: fmt::Debug + 'static,
+        T: fmt::Debug + 'static,
+    {
+        match check {
+            Ok(_) => Ok(()),
+            Err(mismatch) => Err(ApproEqError::ComponentError(Box::new(mismatch))),
+        }
+    }
+
+    /// The length of `Debug` output above which the values are truncated in a failure message.
+    const DEBUG_LIMIT: usize = 256;
+
+    struct Truncated<'a, V: ?Sized>(&'a V);
+
+    impl<V: fmt::Debug + ?Sized> fmt::Display for Truncated<'_, V> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let debug = format!("{:?}", self.0);
+            match debug.char_indices().nth(DEBUG_LIMIT) {
+                Some((end, _)) => write!(
+                    f,
+                    "{}... ({} more characters)",
+                    &debug[..end],
+                    debug[end..].chars().count()
+                ),
+                None => write!(f, "{}", debug),
+            }
+        }
+    }
+
+    struct Describe<'a, E, T>(&'a Mismatch<E, T>, bool);
+
+    impl<E: fmt::Debug, T: fmt::Debug> fmt::Display for Describe<'_, E, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.describe(f, self.1)
+        }
+    }
+
+    struct Within<'a, E, T>(&'a Report<E, T>, bool);
+
+    impl<E: fmt::Debug, T: fmt::Debug> fmt::Display for Within<'_, E, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Within(report, default) = self;
+            let default = if *default { "default " } else { "" };
+            match &report.error {
+                Some(error) => write!(
+                    f,
+                    "error `{:?}` is within {}tolerance `{:?}`",
+                    error, default, report.tol
+                )?,
+                None => write!(
+                    f,
+                    "no error measured, {}tolerance `{:?}`",
+                    default, report.tol
+                )?,
+            }
+            if !report.path.is_empty() {
+                write!(f, " at ")?;
+                for segment in &report.path {
+                    write!(f, "{}", segment)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[track_caller]
+    fn panic<L, R, D>(
+        op: &str,
+        left: &L,
+        right: &R,
+        error: D,
+        message: Option<fmt::Arguments<'_>>,
+    ) -> !
+    where
+        L: fmt::Debug + ?Sized,
+        R: fmt::Debug + ?Sized,
+        D: fmt::Display,
+    {
+        let (separator, message) = match message {
+            Some(message) => (": ", message.to_string()),
+            None => ("", String::new()),
+        };
+        panic!(
+            "assertion failed: `(left {} right)`{}{}\n  left: `{}`,\n right: `{}`,\n error: {}",
+            op,
+            separator,
+            message,
+            Truncated(left),
+            Truncated(right),
+            error
+        )
+    }
+
+    /// Panics with a message describing `mismatch`, where `default` tells that the tolerance was not given,
+    /// following `message` if any.
+    #[track_caller]
+    pub fn fail<L, R, E, T>(
+        left: &L,
+        right: &R,
+        mismatch: &Mismatch<E, T>,
+        default: bool,
+        message: Option<fmt::Arguments<'_>>,
+    ) -> !
+    where
+        L: fmt::Debug + ?Sized,
+        R: fmt::Debug + ?Sized,
+        E: fmt::Debug,
+        T: fmt::Debug,
+    {
+        panic("==", left, right, Describe(mismatch, default), message)
+    }
+
+    /// Panics with a message describing `report` of values that were expected not to be approximately equal.
+    #[track_caller]
+    pub fn fail_ne<L, R, E, T>(
+        left: &L,
+        right: &R,
+        report: &Report<E, T>,
+        default: bool,
+        message: Option<fmt::Arguments<'_>>,
+    ) -> !
+    where
+        L: fmt::Debug + ?Sized,
+        R: fmt::Debug + ?Sized,
+        E: fmt::Debug,
+        T: fmt::Debug,
+    {
+        panic("!=", left, right, Within(report, default), message)
+    }
+}
@@ -0,0 +1,180 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::AbsError;
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::RelError;
+use crate::RelErrorDef;
+use crate::RelTolerance;
+use crate::Tol;
+use crate::TolError;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A point in time measured from an origin, compared as the `Duration` elapsed since it.
+///
+/// Relative errors are then relative to the elapsed time rather than to the clock,
+/// and an `Instant` can be compared with a `SystemTime` when each is measured from an origin of its own clock.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::Elapsed;
+/// # use std::time::{Duration, Instant};
+/// # fn main() {
+/// let start = Instant::now();
+/// let fired = start + Duration::from_millis(1020);
+/// assert_appro_eq_rel!(Elapsed::new(start, fired), Duration::from_secs(1), 0.05); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Elapsed<T> {
+    origin: T,
+    time: T,
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<T> Elapsed<T> {
+    /// Measures `time` from `origin`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn new(origin: T, time: T) -> Elapsed<T> {
+        Elapsed { origin, time }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Elapsed<Instant> {
+    /// The time elapsed since the origin,
+    /// or `ApproEqError::BeforeOrigin` if the time is earlier than the origin.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn duration(&self) -> Result<Duration, ApproEqError> {
+        self.time
+            .checked_duration_since(self.origin)
+            .ok_or_else(|| ApproEqError::BeforeOrigin(self.origin - self.time))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl Elapsed<SystemTime> {
+    /// The time elapsed since the origin,
+    /// or `ApproEqError::BeforeOrigin` if the time is earlier than the origin.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn duration(&self) -> Result<Duration, ApproEqError> {
+        self.time
+            .duration_since(self.origin)
+            .map_err(|e| ApproEqError::BeforeOrigin(e.duration()))
+    }
+}
+
+/// The time elapsed in a compared value.
+trait Elapse {
+    fn elapsed(&self) -> Result<Duration, ApproEqError>;
+}
+
+impl Elapse for Elapsed<Instant> {
+    fn elapsed(&self) -> Result<Duration, ApproEqError> {
+        self.duration()
+    }
+}
+
+impl Elapse for Elapsed<SystemTime> {
+    fn elapsed(&self) -> Result<Duration, ApproEqError> {
+        self.duration()
+    }
+}
+
+impl Elapse for Duration {
+    fn elapsed(&self) -> Result<Duration, ApproEqError> {
+        Ok(*self)
+    }
+}
+
+/// relative tolerance is that of the elapsed `Duration`
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<T> RelTolerance<f64> for Elapsed<T> {
+    fn rel_tolerance() -> f64 {
+        <Duration as RelTolerance<f64>>::rel_tolerance()
+    }
+}
+
+/// Implements the comparisons of an `Elapsed` with the time elapsed in `$rhs`.
+macro_rules! elapsed_impls {
+    ($T:ty, $rhs:ty) => {
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl AbsError<$rhs, Duration> for Elapsed<$T> {
+            fn abs_error(&self, expected: &$rhs) -> ApproEqResult<Duration> {
+                self.elapsed()?.abs_error(&expected.elapsed()?)
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl RelError<$rhs, Duration> for Elapsed<$T> {
+            fn rel_error(&self, expected: &$rhs) -> ApproEqResult<Duration> {
+                self.rel_error_with_def(expected, RelErrorDef::default())
+            }
+
+            fn rel_error_with_def(
+                &self,
+                expected: &$rhs,
+                def: RelErrorDef,
+            ) -> ApproEqResult<Duration> {
+                self.elapsed()?
+                    .rel_error_with_def(&expected.elapsed()?, def)
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl RelError<$rhs, f64> for Elapsed<$T> {
+            fn rel_error(&self, expected: &$rhs) -> ApproEqResult<f64> {
+                self.rel_error_with_def(expected, RelErrorDef::default())
+            }
+
+            fn rel_error_with_def(&self, expected: &$rhs, def: RelErrorDef) -> ApproEqResult<f64> {
+                self.elapsed()?
+                    .rel_error_with_def(&expected.elapsed()?, def)
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+        impl TolError<$rhs, Duration> for Elapsed<$T> {
+            fn tol_error(&self, expected: &$rhs, tol: &Tol<Duration>) -> ApproEqResult<f64> {
+                self.elapsed()?.tol_error(&expected.elapsed()?, tol)
+            }
+        }
+    };
+}
+
+elapsed_impls!(Instant, Elapsed<Instant>);
+elapsed_impls!(Instant, Elapsed<SystemTime>);
+elapsed_impls!(Instant, Duration);
+elapsed_impls!(SystemTime, Elapsed<SystemTime>);
+elapsed_impls!(SystemTime, Elapsed<Instant>);
+elapsed_impls!(SystemTime, Duration);
+
+/// The `SystemTime` a `Duration` since the UNIX epoch is,
+/// or `ApproEqError::Overflow` if it cannot be represented.
+fn unix_time(since_epoch: &Duration) -> Result<SystemTime, ApproEqError> {
+    UNIX_EPOCH
+        .checked_add(*since_epoch)
+        .ok_or(ApproEqError::Overflow)
+}
+
+/// Compares with a `Duration` since the UNIX epoch.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError<Duration, Duration> for SystemTime {
+    fn abs_error(&self, expected: &Duration) -> ApproEqResult<Duration> {
+        self.abs_error(&unix_time(expected)?)
+    }
+}
+
+/// Compares a `Duration` since the UNIX epoch with a `SystemTime`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl AbsError<SystemTime, Duration> for Duration {
+    fn abs_error(&self, expected: &SystemTime) -> ApproEqResult<Duration> {
+        unix_time(self)?.abs_error(expected)
+    }
+}
@@ -0,0 +1,253 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::config;
+use crate::ApproEqResult;
+use crate::FloatPolicy;
+use crate::RelErrorDef;
+use std::any::Any;
+use std::cell::RefCell;
+
+thread_local!(static OVERRIDES: RefCell<Vec<Box<dyn Any>>> = RefCell::new(Vec::new()));
+
+/// Tolerance for approximately equality comparisons, as a value.
+///
+/// Two values are approximately equal when any of the criteria that are set holds:
+/// the absolute error is within `abs`, the relative error(as defined by `rel_def`) is within `rel`,
+/// or the values are within `ulps` units in the last place. Collections are compared element by element.
+/// When no criterion is set, `AbsTolerance::abs_tolerance()` of the element type is used as `abs`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::Tol;
+/// # fn main() {
+/// let tol = Tol::new().abs(1e-9f64).rel(1e-6);
+/// assert_appro_eq!(vec![0f64, 1e6], vec![1e-12f64, 1e6 + 0.5], tol = tol); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tol<D> {
+    /// Absolute tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub abs: Option<D>,
+    /// Relative tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub rel: Option<D>,
+    /// Definition of the relative error compared with `rel`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub rel_def: RelErrorDef,
+    /// Tolerance in units in the last place.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub ulps: Option<u64>,
+    /// Policy for NaN, infinities and signed zeros, or `None` to keep the policy in effect.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub policy: Option<FloatPolicy>,
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<D> Default for Tol<D> {
+    fn default() -> Tol<D> {
+        Tol {
+            abs: None,
+            rel: None,
+            rel_def: RelErrorDef::default(),
+            ulps: None,
+            policy: None,
+        }
+    }
+}
+
+impl<D> Tol<D> {
+    /// Creates a tolerance with no criterion set.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn new() -> Tol<D> {
+        Tol::default()
+    }
+
+    /// Sets the absolute tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn abs(mut self, abs: D) -> Tol<D> {
+        self.abs = Some(abs);
+        self
+    }
+
+    /// Sets the relative tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn rel(mut self, rel: D) -> Tol<D> {
+        self.rel = Some(rel);
+        self
+    }
+
+    /// Sets the definition of the relative error.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn rel_def(mut self, rel_def: RelErrorDef) -> Tol<D> {
+        self.rel_def = rel_def;
+        self
+    }
+
+    /// Sets the tolerance in units in the last place.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn ulps(mut self, ulps: u64) -> Tol<D> {
+        self.ulps = Some(ulps);
+        self
+    }
+
+    /// Sets the policy for NaN, infinities and signed zeros.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn policy(mut self, policy: FloatPolicy) -> Tol<D> {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Converts the absolute and relative tolerances with `f`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+    pub fn map<E, F: Fn(&D) -> E>(&self, f: F) -> Tol<E> {
+        Tol {
+            abs: self.abs.as_ref().map(&f),
+            rel: self.rel.as_ref().map(&f),
+            rel_def: self.rel_def,
+            ulps: self.ulps,
+            policy: self.policy,
+        }
+    }
+
+    /// Converts the tolerances with `f`, using `default` as the absolute tolerance when no criterion is set.
+    pub(crate) fn to_f64<F: Fn(&D) -> f64, G: FnOnce() -> D>(&self, f: F, default: G) -> Tol<f64> {
+        let mut tol = self.map(&f);
+        if tol.abs.is_none() && tol.rel.is_none() && tol.ulps.is_none() {
+            tol.abs = Some(f(&default()));
+        }
+        tol
+    }
+}
+
+impl Tol<f64> {
+    /// Returns the ratio of the error to the tolerance for the most lenient of the criteria that are set,
+    /// which is 1 or less when approximately equal.
+    ///
+    /// `actual` and `expected` are the magnitudes of the values, and `ulps` measures their distance in units
+    /// in the last place; it is only called if the ulps tolerance is set.
+    pub(crate) fn ratio<F: FnOnce() -> ApproEqResult<u64>>(
+        &self,
+        diff: f64,
+        actual: f64,
+        expected: f64,
+        ulps: F,
+    ) -> ApproEqResult<f64> {
+        fn scale(error: f64, tol: f64) -> f64 {
+            if error == 0.0 {
+                0.0
+            } else {
+                error / tol
+            }
+        }
+        fn lenient(ratio: Option<f64>, other: f64) -> Option<f64> {
+            Some(ratio.map_or(other, |ratio| ratio.min(other)))
+        }
+
+        let mut ratio = None;
+        if let Some(abs) = self.abs {
+            ratio = lenient(ratio, scale(diff, abs));
+        }
+        if let Some(rel) = self.rel {
+            let base = self.rel_def.base(actual, expected, |a, b| (a + b) / 2.0);
+            ratio = lenient(ratio, scale(diff, rel * base));
+        }
+        if let Some(tol) = self.ulps {
+            if let Some(ulps) = ulps()? {
+                ratio = lenient(ratio, scale(ulps as f64, tol as f64));
+            }
+        }
+        Ok(ratio)
+    }
+}
+
+struct Pop;
+
+impl Drop for Pop {
+    fn drop(&mut self) {
+        OVERRIDES.with(|overrides| overrides.borrow_mut().pop());
+    }
+}
+
+/// Calls `f` with the default tolerances of `D` overridden on the current thread.
+///
+/// `tol.abs` and `tol.rel`, if set, are returned by `AbsTolerance::abs_tolerance()` and
+/// `RelTolerance::rel_tolerance()` of `D` instead of the defaults, for `D` of `f32`, `f64`,
+/// the primitive integers and `Duration`. `tol.ulps`, if set, is returned by `UlpsTolerance::ulps_tolerance()`
+/// for the distances of `D` of `f32` and `f64`, saturating at `u32::MAX` for `f32`, and `tol.policy`, if set,
+/// is in effect as by `FloatPolicy::scope`. Overrides nest, an inner one replacing only the tolerances it sets.
+/// They are removed when `f` returns or panics.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate appro_eq;
+/// # use appro_eq::Tol;
+/// # fn main() {
+/// appro_eq::with_tolerance(Tol::new().abs(1e-6f64), || {
+///     assert_appro_eq!(1f64, 1.0000001f64); // does not panic
+/// });
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+pub fn with_tolerance<D: 'static, R, F: FnOnce() -> R>(tol: Tol<D>, f: F) -> R {
+    let policy = tol.policy;
+    OVERRIDES.with(|overrides| overrides.borrow_mut().push(Box::new(tol)));
+    let _pop = Pop;
+    match policy {
+        Some(policy) => policy.scope(f),
+        None => f(),
+    }
+}
+
+fn overridden<D: 'static, T: Clone>(field: fn(&Tol<D>) -> &Option<T>) -> Option<T> {
+    OVERRIDES.with(|overrides| {
+        overrides
+            .borrow()
+            .iter()
+            .rev()
+            .filter_map(|tol| tol.downcast_ref::<Tol<D>>())
+            .find_map(|tol| field(tol).clone())
+    })
+}
+
+/// Returns the ulps tolerance for the distances of `D` overridden by `with_tolerance`, if any.
+pub(crate) fn ulps_tolerance<D: 'static>() -> Option<u64> {
+    overridden(|tol: &Tol<D>| &tol.ulps)
+}
+
+/// The default tolerance asked for: the absolute or relative tolerance of the blanket implementations of
+/// `AbsTolerance` and `RelTolerance`, or the tolerance of `Tolerance::tolerance()` itself.
+#[doc(hidden)]
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Query {
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    Abs,
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    Rel,
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    Tolerance,
+}
+
+/// Returns the tolerance of `D` asked for by `query`, if set: the absolute or relative tolerance
+/// overridden by `with_tolerance` or configured, or else the configured tolerance.
+pub(crate) fn configured<D: Clone + 'static>(query: Query) -> Option<D> {
+    let tol = match query {
+        Query::Abs => overridden(|tol| &tol.abs).or_else(config::abs),
+        Query::Rel => overridden(|tol| &tol.rel).or_else(config::rel),
+        Query::Tolerance => None,
+    };
+    tol.or_else(config::tolerance)
+}
+
+/// Returns the tolerance of `D` asked for by `query` as `configured`, or `default`.
+pub(crate) fn default_tolerance<D: Clone + 'static>(query: Query, default: D) -> D {
+    configured(query).unwrap_or(default)
+}
@@ -0,0 +1,166 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! http://mozilla.org/MPL/2.0/.
+
+use crate::ApproEqError;
+use crate::ApproEqResult;
+use crate::Outliers;
+use crate::Segment;
+
+/// The path from the values compared to an element, extended step by step as collections are traversed.
+///
+/// The steps are only turned into `Segment`s for the paths that are kept, to the element with the largest error
+/// or to the element that failed to be compared.
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+#[derive(Clone, Copy)]
+pub struct Path<'a>(Option<(&'a Path<'a>, &'a dyn Fn() -> Segment)>);
+
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+impl Path<'static> {
+    /// The path to the values compared themselves.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    pub const ROOT: Path<'static> = Path(None);
+}
+
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+impl<'a> Path<'a> {
+    /// The path to an element of the collection at this path, one step further as made by `segment`.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    pub fn join(&'a self, segment: &'a dyn Fn() -> Segment) -> Path<'a> {
+        Path(Some((self, segment)))
+    }
+
+    /// Whether this is the path to the values compared themselves.
+    pub(crate) fn is_root(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Replaces the contents of `segments` with the segments of this path.
+    pub(crate) fn write(&self, segments: &mut Vec<Segment>) {
+        match self.0 {
+            Some((parent, segment)) => {
+                parent.write(segments);
+                segments.push(segment());
+            }
+            None => segments.clear(),
+        }
+    }
+
+    /// The segments of this path.
+    pub(crate) fn segments(&self) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        self.write(&mut segments);
+        segments
+    }
+
+    /// Passes `visitor` the error of the element at `segments` from this path.
+    pub(crate) fn visit_at<D>(&self, segments: &[Segment], error: D, visitor: &mut dyn Visitor<D>) {
+        match segments.split_first() {
+            Some((segment, rest)) => {
+                let step = || segment.clone();
+                self.join(&step).visit_at(rest, error, visitor);
+            }
+            None => visitor.visit(self, error),
+        }
+    }
+
+    /// Locates `error` at the element at the end of this path, in front of the path it already has.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    pub fn locate(&self, error: ApproEqError) -> ApproEqError {
+        self.locate_at(Vec::new(), error)
+    }
+
+    /// Locates `error` at the element at `segments` from this path, in front of the path it already has.
+    pub(crate) fn locate_at(&self, segments: Vec<Segment>, error: ApproEqError) -> ApproEqError {
+        if self.is_root() && segments.is_empty() {
+            return error;
+        }
+        let (path, cause) = error.split();
+        let mut full = self.segments();
+        full.extend(segments);
+        full.extend(path);
+        ApproEqError::At {
+            path: full,
+            cause: Box::new(cause),
+        }
+    }
+}
+
+/// Receives the errors of the elements of the values compared, as the collections are traversed.
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+pub trait Visitor<D> {
+    /// Receives the error of the element at `path`.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn visit(&mut self, path: &Path<'_>, error: D);
+
+    /// Whether the visitor counts the elements exceeding the tolerance against the `outliers` allowed
+    /// among the elements visited next, rather than receiving the error left once they are left out.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn allow(&mut self, outliers: &Outliers) -> bool {
+        let _ = outliers;
+        false
+    }
+
+    /// Receives the path to an element whose error exceeds the cap of the outliers allowed.
+    #[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+    fn over_cap(&mut self, path: &Path<'_>) {
+        let _ = path;
+    }
+}
+
+/// Collects the errors visited, with the paths to them if `paths` is set.
+pub(crate) struct Collect<D> {
+    pub(crate) paths: bool,
+    pub(crate) errors: Vec<(D, Vec<Segment>)>,
+}
+
+impl<D> Visitor<D> for Collect<D> {
+    fn visit(&mut self, path: &Path<'_>, error: D) {
+        let segments = if self.paths {
+            path.segments()
+        } else {
+            Vec::new()
+        };
+        self.errors.push((error, segments));
+    }
+}
+
+/// Passes `visitor` the error of a value measured as a whole, or fails with the error located at `path`.
+#[cfg_attr(feature = "docs", unstable(feature = "support", issue = "none"))]
+pub fn visit<D>(
+    result: ApproEqResult<D>,
+    path: &Path<'_>,
+    visitor: &mut dyn Visitor<D>,
+) -> Result<(), ApproEqError> {
+    match result {
+        Ok(Some(error)) => {
+            visitor.visit(path, error);
+            Ok(())
+        }
+        Ok(None) => Ok(()),
+        Err(e) => Err(path.locate(e)),
+    }
+}
+
+/// Visits the pairs of elements in `pairs` with `element`, at the path one step further from `path`,
+/// where `segment` maps the position of a pair to the step.
+///
+/// The first element failing to be compared fails the whole.
+pub(crate) fn each<T, I, F, E>(
+    pairs: I,
+    path: &Path<'_>,
+    segment: F,
+    mut element: E,
+) -> Result<(), ApproEqError>
+where
+    I: Iterator<Item = T>,
+    F: Fn(usize) -> Segment,
+    E: FnMut(T, &Path<'_>) -> Result<(), ApproEqError>,
+{
+    for (i, pair) in pairs.enumerate() {
+        let step = || segment(i);
+        element(pair, &path.join(&step))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+#[macro_use]
+extern crate appro_eq;
+
+use appro_eq::{AbsTolerance, RelTolerance, Tolerance};
+use std::env;
+use std::fs;
+use std::time::Duration;
+
+// The configuration is loaded once per process, so everything is checked in a single test.
+#[test]
+fn config_overrides_defaults() {
+    let path = env::temp_dir().join(format!("appro-eq-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        "# defaults for CI\n\
+         [f64]\n\
+         abs = 1e-9\n\
+         rel = \"1e-6\" # quoted\n\
+         \n\
+         [f32]\n\
+         tolerance = 1e-3\n\
+         \n\
+         [duration]\n\
+         abs = 0.5\n",
+    )
+    .unwrap();
+    env::set_var("APPRO_EQ_CONFIG", &path);
+    env::set_var("APPRO_EQ_F64_REL", "1e-4");
+    env::set_var("APPRO_EQ_I32", "2");
+    env::set_var("APPRO_EQ_DURATION", "0.01");
+    env::set_var("APPRO_EQ_F46_ABS", "1");
+    env::set_var("APPRO_EQ_LOG", "verbose");
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        env::set_var(OsStr::from_bytes(b"APPRO_EQ_\xff"), "1");
+        env::set_var("OTHER", OsStr::from_bytes(b"\xff"));
+    }
+
+    assert_eq!(f64::abs_tolerance(), 1e-9);
+    assert_eq!(f64::rel_tolerance(), 1e-4);
+    assert_eq!(f64::tolerance(), 1e-11);
+    assert_eq!(f32::tolerance(), 1e-3);
+    assert_eq!(f32::abs_tolerance(), 1e-3);
+    assert_eq!(i32::abs_tolerance(), 2);
+    assert_eq!(i64::abs_tolerance(), 0);
+    assert_eq!(Duration::abs_tolerance(), Duration::from_millis(500));
+    assert_eq!(
+        <Duration as RelTolerance>::rel_tolerance(),
+        Duration::from_millis(10)
+    );
+    assert_eq!(<Duration as RelTolerance<f64>>::rel_tolerance(), 0.01);
+
+    assert_appro_eq!(1f64, 1f64 + 1e-10);
+    assert_appro_eq_rel!(1000f64, 1000.05f64);
+    assert_appro_eq!(10i32, 12i32);
+    assert_appro_eq!(Duration::from_secs(1), Duration::from_millis(1400));
+    appro_eq::with_tolerance(appro_eq::Tol::new().abs(1e-12f64), || {
+        assert_eq!(f64::abs_tolerance(), 1e-12);
+    });
+
+    fs::remove_file(&path).unwrap();
+}
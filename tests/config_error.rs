@@ -0,0 +1,16 @@
+use appro_eq::{AbsTolerance, RelTolerance};
+use std::env;
+use std::fs;
+
+#[test]
+fn config_parse_error() {
+    let path = env::temp_dir().join(format!("appro-eq-error-{}.toml", std::process::id()));
+    fs::write(&path, "[f64]\nabs = 1e-9\nrel = small\n").unwrap();
+    env::set_var("APPRO_EQ_CONFIG", &path);
+    env::set_var("APPRO_EQ_F32_ABS", "small");
+    let abs = f64::abs_tolerance();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(abs, 1e-9);
+    assert_eq!(f64::rel_tolerance(), 1e-11);
+    assert_eq!(f32::abs_tolerance(), 1e-6);
+}
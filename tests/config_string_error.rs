@@ -0,0 +1,14 @@
+use appro_eq::AbsTolerance;
+use std::env;
+use std::fs;
+
+#[test]
+fn config_string_error() {
+    let path = env::temp_dir().join(format!("appro-eq-string-{}.toml", std::process::id()));
+    fs::write(&path, "[f64]\nabs = \"1e-9 #\"\n[f32]\nabs = 1e-3\n").unwrap();
+    env::set_var("APPRO_EQ_CONFIG", &path);
+    let abs = f64::abs_tolerance();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(abs, 1e-11);
+    assert_eq!(f32::abs_tolerance(), 1e-3);
+}
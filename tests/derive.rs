@@ -0,0 +1,188 @@
+#![cfg(feature = "derive")]
+
+#[macro_use]
+extern crate appro_eq;
+
+use appro_eq::{abs_appro_check, rel_appro_check, AbsError, ApproEqError, RelError, Segment};
+
+#[derive(Debug, AbsError, RelError)]
+struct Point {
+    x: f64,
+    y: f64,
+    samples: Vec<f64>,
+}
+
+#[derive(Debug, AbsError, RelError)]
+struct Pair(f64, f64);
+
+#[derive(Debug, AbsError)]
+struct Unit;
+
+#[derive(Debug, AbsError, RelError)]
+struct Annotated {
+    value: f64,
+    #[appro_eq(skip)]
+    label: &'static str,
+    #[appro_eq(exact)]
+    id: u32,
+    #[appro_eq(abs = 0.5, rel = 0.1)]
+    coarse: f64,
+}
+
+#[derive(Debug, AbsError, RelError)]
+enum Shape {
+    Circle { radius: f64 },
+    Rect(f64, f64),
+    Empty,
+}
+
+#[derive(Debug, AbsError)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(Debug, AbsError, RelError)]
+struct Tagged<'a, T, const N: usize> {
+    values: T,
+    #[appro_eq(abs = 0.5, rel = 0.1)]
+    coarse: [f64; N],
+    #[appro_eq(skip)]
+    label: &'a str,
+}
+
+fn point(y: f64) -> Point {
+    Point {
+        x: 1.0,
+        y,
+        samples: vec![1.0, 2.0, 3.0],
+    }
+}
+
+#[test]
+fn named_struct() {
+    assert_appro_eq!(point(2.0), point(2.0 + 1e-12));
+    assert_appro_eq_abs!(point(2.0), point(2.5), 0.6);
+    assert_eq!(point(2.0).abs_error(&point(2.5)).unwrap(), Some(0.5));
+
+    let mut actual = point(2.0);
+    actual.samples[2] = 3.25;
+    let mismatch = abs_appro_check(&actual, &point(2.0), &0.1).unwrap_err();
+    assert_eq!(mismatch.error, Some(0.25));
+    assert_eq!(
+        mismatch.path,
+        vec![Segment::Field("samples"), Segment::Index(2)]
+    );
+    assert!(mismatch.to_string().ends_with(" at .samples[2]"));
+}
+
+#[test]
+fn tuple_and_unit_struct() {
+    assert_appro_eq!(Pair(1.0, 2.0), Pair(1.0, 2.0 + 1e-12));
+    let mismatch = rel_appro_check(&Pair(1.0, 2.0), &Pair(1.0, 3.0), &0.1).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Field("1")]);
+    assert_eq!(
+        AbsError::<Unit, f64>::abs_error(&Unit, &Unit).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn field_attributes() {
+    let annotated = |value, label, id, coarse| Annotated {
+        value,
+        label,
+        id,
+        coarse,
+    };
+    let (a, b) = (annotated(1.0, "a", 7, 1.0), annotated(1.0, "b", 7, 1.4));
+    assert_ne!(a.label, b.label);
+    assert_appro_eq_abs!(a, b, 1e-6);
+    assert_appro_eq_rel!(
+        annotated(1.0, "a", 7, 1.0),
+        annotated(1.0, "b", 7, 1.05),
+        1e-6
+    );
+
+    let mismatch = abs_appro_check(
+        &annotated(1.0, "a", 7, 1.0),
+        &annotated(1.0, "a", 8, 1.0),
+        &1.0,
+    )
+    .unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::ValueMismatch)));
+    assert_eq!(mismatch.path, vec![Segment::Field("id")]);
+
+    let mismatch = abs_appro_check(
+        &annotated(1.0, "a", 7, 1.0),
+        &annotated(1.0, "a", 7, 2.0),
+        &1.0,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::ComponentError(_))
+    ));
+    assert_eq!(mismatch.path, vec![Segment::Field("coarse")]);
+}
+
+#[test]
+fn enums() {
+    assert_appro_eq!(
+        Shape::Circle { radius: 1.0 },
+        Shape::Circle {
+            radius: 1.0 + 1e-12
+        }
+    );
+    assert_appro_eq!(Shape::Rect(1.0, 2.0), Shape::Rect(1.0, 2.0));
+    assert_appro_eq!(Shape::Empty, Shape::Empty);
+    assert_appro_ne!(Shape::Rect(1.0, 2.0), Shape::Rect(1.0, 3.0));
+
+    let mismatch = abs_appro_check(&Shape::Empty, &Shape::Rect(1.0, 2.0), &1.0).unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::VariantMismatch)
+    ));
+    assert!(mismatch.path.is_empty());
+}
+
+#[test]
+fn generic_struct() {
+    assert_appro_eq_abs!(
+        Wrapper {
+            inner: vec![1f32, 2.0]
+        },
+        Wrapper {
+            inner: vec![1f32, 2.1]
+        },
+        0.2
+    );
+}
+
+#[test]
+fn generic_struct_with_tolerance_field() {
+    let tagged = |values: Vec<f64>, coarse: [f64; 2], label| Tagged {
+        values,
+        coarse,
+        label,
+    };
+    let actual = tagged(vec![1.0], [1.0, 2.0], "actual");
+    let expected = tagged(vec![1.0 + 1e-12], [1.25, 2.0], "expected");
+    assert_ne!(actual.label, expected.label);
+    assert_appro_eq_abs!(actual, expected);
+    assert_appro_eq_rel!(
+        tagged(vec![1.0], [1.0, 2.0], "actual"),
+        tagged(vec![1.0 + 1e-12], [1.05, 2.0], "expected"),
+        1e-9
+    );
+    let mismatch = abs_appro_check(
+        &tagged(vec![1.0], [1.0, 2.0], "actual"),
+        &tagged(vec![1.0], [1.0, 3.0], "expected"),
+        &1.0,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::ComponentError(_))
+    ));
+    assert_eq!(mismatch.path, vec![Segment::Field("coarse")]);
+}
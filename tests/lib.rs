@@ -1,3 +1,5 @@
+#![allow(deprecated, clippy::legacy_numeric_constants)]
+
 #[macro_use]
 extern crate appro_eq;
 
@@ -8,12 +10,47 @@ use num_complex::Complex;
 use num_rational::Rational;
 
 #[cfg(feature = "ndarray")]
-use ndarray::{arr1, arr2, arr3, ArrayD, IxDyn};
-
+use ndarray::{arr0, arr1, arr2, arr3, ArrayD, IxDyn};
+
+use appro_eq::{
+    abs_appro_check, mixed_appro_check, rel_appro_check, rel_appro_check_with_def, tol_appro_check,
+    tols_appro_check, ulps_appro_check, with_tolerance, AbsApproEq, AbsApproEqWithTol, AbsError,
+    AbsTolerance, ApproEqError, Elapsed, FloatPolicy, Magnitude, Norm, Outliers, RelApproEq,
+    RelApproEqWithTol, RelError, RelErrorDef, RelTolerance, Segment, Tol, TolApproEq, Tolerance,
+    UlpsApproEq, Weighted,
+};
+use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::rc::Rc;
-use std::sync::Arc;
-use std::time::{Duration, Instant, SystemTime};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const NAN_EQ: FloatPolicy = FloatPolicy {
+    nan_eq: true,
+    inf_eq: false,
+    signed_zero: false,
+};
+
+const INF_EQ: FloatPolicy = FloatPolicy {
+    nan_eq: false,
+    inf_eq: true,
+    signed_zero: false,
+};
+
+const SIGNED_ZERO: FloatPolicy = FloatPolicy {
+    nan_eq: false,
+    inf_eq: false,
+    signed_zero: true,
+};
+
+const FIXTURE_TOL: Tol<f64> = Tol {
+    abs: Some(1e-9),
+    rel: Some(1e-6),
+    rel_def: RelErrorDef::Expected,
+    ulps: None,
+    policy: None,
+};
 
 macro_rules! panic_test_rel {
     ($name:ident, $($arg:tt)*) => (
@@ -78,6 +115,48 @@ macro_rules! panic_test_none {
     )
 }
 
+macro_rules! panic_test_ulps {
+    ($name:ident, $($arg:tt)*) => (
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            #[should_panic]
+            fn normal() {
+                assert_appro_eq_ulps!($($arg)*);
+            }
+
+            #[test]
+            #[cfg_attr(debug_assertions, should_panic)]
+            fn debug() {
+                debug_assert_appro_eq_ulps!($($arg)*);
+            }
+        }
+    )
+}
+
+macro_rules! panic_test_mixed {
+    ($name:ident, $($arg:tt)*) => (
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            #[should_panic]
+            fn normal() {
+                assert_appro_eq_mixed!($($arg)*);
+            }
+
+            #[test]
+            #[cfg_attr(debug_assertions, should_panic)]
+            fn debug() {
+                debug_assert_appro_eq_mixed!($($arg)*);
+            }
+        }
+    )
+}
+
 macro_rules! panic_test_all {
     ($name:ident, $($arg:tt)*) => (
         mod $name {
@@ -147,6 +226,44 @@ macro_rules! ok_test_none {
     )
 }
 
+macro_rules! ok_test_ulps {
+    ($name:ident, $($arg:tt)*) => (
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn normal() {
+                assert_appro_eq_ulps!($($arg)*);
+            }
+
+            #[test]
+            fn debug() {
+                debug_assert_appro_eq_ulps!($($arg)*);
+            }
+        }
+    )
+}
+
+macro_rules! ok_test_mixed {
+    ($name:ident, $($arg:tt)*) => (
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn normal() {
+                assert_appro_eq_mixed!($($arg)*);
+            }
+
+            #[test]
+            fn debug() {
+                debug_assert_appro_eq_mixed!($($arg)*);
+            }
+        }
+    )
+}
+
 macro_rules! ok_test_all {
     ($name:ident, $($arg:tt)*) => (
         mod $name {
@@ -177,9 +294,145 @@ panic_test_all!(
     8f32 - 1e-5
 );
 panic_test_all!(bad_compare_with_explicit_eps, 3f64, 4f64, 1e-3f64);
+panic_test_rel!(bad_compare_with_rel_negative_f32, -1f32, -2f32);
+panic_test_rel!(bad_compare_with_rel_negative_f64, -1f64, -2f64);
+panic_test_rel!(bad_compare_with_rel_negative_expected, 10f64, -1f64, 1f64);
+ok_test_rel!(
+    compare_with_rel_def_expected,
+    1f64,
+    2f64,
+    0.5f64,
+    RelErrorDef::Expected
+);
+ok_test_rel!(
+    compare_with_rel_def_max,
+    1f64,
+    -3f64,
+    1.34f64,
+    RelErrorDef::Max
+);
+ok_test_rel!(
+    compare_with_rel_def_mean,
+    1f64,
+    3f64,
+    1f64,
+    RelErrorDef::Mean
+);
+ok_test_rel!(
+    compare_with_rel_def_min,
+    -1f64,
+    -3f64,
+    2f64,
+    RelErrorDef::Min
+);
+ok_test_rel!(
+    compare_with_rel_def_max_zero,
+    0f64,
+    1e-300f64,
+    1f64,
+    RelErrorDef::Max
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_expected,
+    1f64,
+    2f64,
+    0.49f64,
+    RelErrorDef::Expected
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_max,
+    1f64,
+    -3f64,
+    1.33f64,
+    RelErrorDef::Max
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_mean,
+    1f64,
+    3f64,
+    0.99f64,
+    RelErrorDef::Mean
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_min,
+    -1f64,
+    -3f64,
+    1.99f64,
+    RelErrorDef::Min
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_max_both_zero,
+    0f64,
+    0f64,
+    1f64,
+    RelErrorDef::Max
+);
+ok_test_rel!(
+    compare_with_rel_def_vector,
+    vec![1f64, -10.0],
+    vec![2f64, -11.0],
+    0.5f64,
+    RelErrorDef::Max
+);
+ok_test_rel!(
+    compare_with_rel_def_i32,
+    100i32,
+    50i32,
+    1i32,
+    RelErrorDef::Max
+);
+panic_test_rel!(
+    bad_compare_with_rel_def_i32,
+    100i32,
+    50i32,
+    0i32,
+    RelErrorDef::Min
+);
+panic_test_rel!(bad_compare_with_rel_negative_i32, -100i32, -20i32, 1i32);
+ok_test_rel!(
+    compare_with_rel_def_u32,
+    100u32,
+    50u32,
+    1u32,
+    RelErrorDef::Mean
+);
+ok_test_rel!(
+    compare_with_rel_def_duration,
+    Duration::new(20, 0),
+    Duration::new(10, 0),
+    Duration::new(0, 500_000_000),
+    RelErrorDef::Max
+);
 panic_test_rel!(bad_compare_with_rel_div_zero_f32, 3f32, 0f32);
 panic_test_rel!(bad_compare_with_rel_div_zero_f64, 3f64, 0f64);
 
+struct Percent(f64);
+
+impl RelError<Percent, f64> for Percent {
+    fn rel_error(&self, expected: &Percent) -> Result<Option<f64>, ApproEqError> {
+        self.0.rel_error(&expected.0)
+    }
+
+    fn rel_error_with_def(
+        &self,
+        expected: &Percent,
+        def: RelErrorDef,
+    ) -> Result<Option<f64>, ApproEqError> {
+        self.0.rel_error_with_def(&expected.0, def)
+    }
+}
+
+#[test]
+fn rel_error_with_def_of_custom_type() {
+    let error = Percent(101.0).rel_error_with_def(&Percent(100.0), RelErrorDef::Expected);
+    assert_eq!(error.unwrap(), Some(0.01));
+    let error = Percent(100.0).rel_error_with_def(&Percent(200.0), RelErrorDef::Min);
+    assert_eq!(error.unwrap(), Some(1.0));
+    assert!(Percent(101.0).rel_appro_eq_with_tol_def(&Percent(100.0), &0.02, RelErrorDef::Expected));
+    assert!(Percent(101.0).rel_appro_eq_with_tol_def(&Percent(100.0), &0.02, RelErrorDef::Max));
+    assert!(!Percent(100.0).rel_appro_eq_with_tol_def(&Percent(200.0), &0.6, RelErrorDef::Min));
+}
+
 ok_test_all!(
     it_should_not_panic_if_values_are_appro_equal_f32_f64,
     8f32,
@@ -297,6 +550,966 @@ panic_test_all!(
     Complex::new(1.0f64, 1e-8)
 );
 
+ok_test_ulps!(compare_ulps_with_adjacent_f32, 1f32, 1f32 + f32::EPSILON);
+ok_test_ulps!(compare_ulps_with_adjacent_f64, 1f64, 1f64 + f64::EPSILON);
+ok_test_ulps!(compare_ulps_across_zero, -0f64, 0f64);
+ok_test_ulps!(
+    compare_ulps_across_zero_with_explicit_eps,
+    -1e-323f64,
+    1e-323f64,
+    4u64
+);
+ok_test_ulps!(compare_ulps_with_large_values, 1e300f64, 1e300f64);
+ok_test_ulps!(
+    compare_ulps_with_explicit_eps,
+    1f64,
+    1f64 + 10.0 * f64::EPSILON,
+    10u64
+);
+ok_test_ulps!(
+    compare_ulps_with_vector,
+    vec![1f64, 2.0, 4.0],
+    vec![1f64 + f64::EPSILON, 2.0, 4.0 - 4.0 * f64::EPSILON]
+);
+ok_test_ulps!(
+    compare_ulps_with_array,
+    [1f32, 2.0, 4.0],
+    [1f32 + f32::EPSILON, 2.0, 4.0]
+);
+ok_test_ulps!(compare_ulps_with_option, Some(1f64), Some(1f64));
+ok_test_ulps!(compare_ulps_with_rc, Rc::new(1f64), Rc::new(1f64));
+
+panic_test_ulps!(bad_compare_ulps_f32, 1f32, 1f32 + 5.0 * f32::EPSILON);
+panic_test_ulps!(bad_compare_ulps_f64, 1f64, 1f64 + 1e-12);
+panic_test_ulps!(
+    bad_compare_ulps_with_explicit_eps,
+    1f64,
+    1f64 + 2.0 * f64::EPSILON,
+    1u64
+);
+panic_test_ulps!(bad_compare_ulps_across_zero, -1e-300f64, 1e-300f64);
+panic_test_ulps!(bad_compare_ulps_with_nan, f64::NAN, f64::NAN);
+panic_test_ulps!(
+    bad_compare_ulps_with_vector,
+    vec![1f64, 2.0, 4.0],
+    vec![1f64, 2.0, 4.1]
+);
+panic_test_ulps!(
+    bad_len_compare_ulps_with_vector,
+    vec![1f64, 2.0],
+    vec![1f64, 2.0, 4.0]
+);
+panic_test_ulps!(
+    bad_compare_ulps_with_option,
+    Some(1f64),
+    Option::<f64>::None
+);
+
+#[cfg(feature = "num-complex")]
+ok_test_ulps!(
+    compare_ulps_with_complex,
+    Complex::new(1.0f64, -2.0),
+    Complex::new(1.0f64 + f64::EPSILON, -2.0)
+);
+
+#[cfg(feature = "num-complex")]
+panic_test_ulps!(
+    bad_compare_ulps_with_complex,
+    Complex::new(1.0f64, 0.0),
+    Complex::new(1.0f64, 1e-300)
+);
+
+ok_test_mixed!(compare_mixed_near_zero, 0f64, 1e-12f64, 1e-9f64, 1e-6f64);
+ok_test_mixed!(compare_mixed_large, 1e6f64, 1e6f64 + 0.5, 1e-9f64, 1e-6f64);
+ok_test_mixed!(compare_mixed_with_default_eps, 0f64, 1e-12f64);
+ok_test_mixed!(compare_mixed_f32_f64, 0f32, 1e-7f64, 1e-6f32, 1e-6f32);
+ok_test_mixed!(
+    compare_mixed_with_vector,
+    vec![0f64, 1.0, 1e6],
+    vec![1e-12f64, 1.0 + 1e-12, 1e6 + 1e-6]
+);
+ok_test_mixed!(
+    compare_mixed_with_slice,
+    &[0f64, -1e6] as &[f64],
+    &[-1e-10f64, -1e6 - 0.5] as &[f64],
+    1e-9f64,
+    1e-6f64
+);
+ok_test_mixed!(
+    compare_mixed_with_array,
+    [0f32, 1.0, 1e6],
+    [1e-7f32, 1.0, 1e6 + 0.5],
+    1e-6f32,
+    1e-6f32
+);
+
+panic_test_mixed!(bad_compare_mixed_near_zero, 0f64, 1e-3f64, 1e-9f64, 1e-6f64);
+panic_test_mixed!(
+    bad_compare_mixed_large,
+    1e6f64,
+    1e6f64 + 2.0,
+    1e-9f64,
+    1e-6f64
+);
+panic_test_mixed!(
+    bad_compare_mixed_with_vector,
+    vec![0f64, 1.0, 1e6],
+    vec![1e-12f64, 1.1, 1e6]
+);
+panic_test_mixed!(
+    bad_len_compare_mixed_with_vector,
+    vec![0f64, 1.0],
+    vec![0f64, 1.0, 1e6]
+);
+panic_test_mixed!(
+    bad_compare_mixed_with_array,
+    [0f32, 1.0, 1e6],
+    [1e-7f32, 1.0, 1e6 + 2.0],
+    1e-6f32,
+    1e-6f32
+);
+
+#[cfg(feature = "num-complex")]
+ok_test_rel!(
+    compare_with_complex_rel_def,
+    Complex::new(0.0f64, 0.0),
+    Complex::new(0.0f64, 1e-300),
+    1f64,
+    RelErrorDef::Max
+);
+
+#[cfg(feature = "num-complex")]
+panic_test_rel!(
+    bad_compare_with_complex_rel_div_zero,
+    Complex::new(1.0f64, 0.0),
+    Complex::new(0.0f64, 0.0)
+);
+
+panic_test_all!(bad_compare_with_nan, f64::NAN, f64::NAN);
+panic_test_all!(bad_compare_with_nan_f32, f32::NAN, 1f32);
+panic_test_all!(bad_compare_with_nan_explicit_eps, 1f64, f64::NAN, 1e10f64);
+panic_test_all!(
+    bad_compare_with_nan_vector_tail,
+    vec![1f64, 2.0],
+    vec![1f64, f64::NAN]
+);
+ok_test_all!(compare_with_nan_policy, f64::NAN, f64::NAN, policy = NAN_EQ);
+ok_test_all!(
+    compare_with_nan_policy_f32_f64,
+    f32::NAN,
+    f64::NAN,
+    policy = NAN_EQ
+);
+ok_test_all!(
+    compare_with_nan_policy_vector,
+    vec![1f64, f64::NAN, 3.0],
+    vec![1f64, f64::NAN, 3.0],
+    policy = NAN_EQ
+);
+ok_test_all!(
+    compare_with_nan_policy_array,
+    [1f32, f32::NAN],
+    [1f32, f32::NAN],
+    1e-3f32,
+    policy = NAN_EQ
+);
+panic_test_all!(
+    bad_compare_with_nan_policy_one_side,
+    vec![1f64, f64::NAN],
+    vec![1f64, 2.0],
+    policy = NAN_EQ
+);
+ok_test_all!(
+    compare_with_infinity_policy,
+    f64::INFINITY,
+    f64::INFINITY,
+    policy = INF_EQ
+);
+ok_test_all!(
+    compare_with_neg_infinity_policy,
+    f32::NEG_INFINITY,
+    f32::NEG_INFINITY,
+    policy = INF_EQ
+);
+panic_test_all!(bad_compare_with_infinity, f64::INFINITY, f64::INFINITY);
+panic_test_all!(
+    bad_compare_with_opposite_infinity,
+    f64::INFINITY,
+    f64::NEG_INFINITY
+);
+panic_test_all!(bad_compare_with_infinity_finite, 1f64, f64::INFINITY);
+ok_test_abs!(compare_with_signed_zero, -0f64, 0f64);
+panic_test_abs!(
+    bad_compare_with_signed_zero_policy,
+    -0f64,
+    0f64,
+    policy = SIGNED_ZERO
+);
+ok_test_abs!(
+    compare_with_same_signed_zero_policy,
+    -0f64,
+    -0f64,
+    policy = SIGNED_ZERO
+);
+ok_test_rel!(
+    compare_with_nan_policy_rel_def,
+    f64::NAN,
+    f64::NAN,
+    1e-3f64,
+    RelErrorDef::Max,
+    policy = NAN_EQ
+);
+ok_test_ulps!(
+    compare_ulps_with_nan_policy,
+    f64::NAN,
+    f64::NAN,
+    policy = NAN_EQ
+);
+ok_test_ulps!(
+    compare_ulps_with_infinity_policy,
+    f64::INFINITY,
+    f64::INFINITY,
+    policy = INF_EQ
+);
+panic_test_ulps!(bad_compare_ulps_with_infinity, f64::INFINITY, f64::INFINITY);
+panic_test_ulps!(
+    bad_compare_ulps_with_signed_zero_policy,
+    -0f32,
+    0f32,
+    policy = SIGNED_ZERO
+);
+ok_test_mixed!(
+    compare_mixed_with_nan_policy,
+    vec![0f64, f64::NAN],
+    vec![0f64, f64::NAN],
+    1e-9f64,
+    1e-6f64,
+    policy = NAN_EQ
+);
+panic_test_mixed!(bad_compare_mixed_with_nan, f64::NAN, f64::NAN);
+
+#[test]
+fn float_policy_is_restored_after_scope() {
+    FloatPolicy::scope(NAN_EQ, || {
+        assert_eq!(FloatPolicy::current(), NAN_EQ);
+        FloatPolicy::scope(SIGNED_ZERO, || {
+            assert_eq!(FloatPolicy::current(), SIGNED_ZERO);
+        });
+        assert_eq!(FloatPolicy::current(), NAN_EQ);
+    });
+    assert_eq!(FloatPolicy::current(), FloatPolicy::default());
+}
+
+#[test]
+fn float_policy_is_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        assert_appro_eq!(f64::NAN, 1f64, policy = NAN_EQ);
+    });
+    assert!(result.is_err());
+    assert_eq!(FloatPolicy::current(), FloatPolicy::default());
+}
+
+ok_test_none!(
+    compare_with_rms_norm,
+    vec![1.1f64, 2.0, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    0.06,
+    norm = Norm::Rms
+);
+panic_test_none!(
+    bad_compare_with_rms_norm,
+    vec![1.2f64, 2.0, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    0.06,
+    norm = Norm::Rms
+);
+ok_test_abs!(
+    compare_with_mean_norm,
+    vec![1.1f64, 2.1, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    0.06,
+    norm = Norm::Mean,
+    msg = "seed: {}",
+    42
+);
+ok_test_rel!(
+    compare_with_mean_norm_rel,
+    vec![1.1f64, 2.0, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    0.03,
+    RelErrorDef::Expected,
+    norm = Norm::Mean,
+    policy = NAN_EQ
+);
+ok_test_none!(
+    compare_with_tol_and_norm,
+    vec![1.1f64, 2.0, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    tol = Tol::new().abs(0.06),
+    norm = Norm::Rms
+);
+ok_test_mixed!(
+    compare_mixed_with_norm,
+    vec![1.1f64, 2.0, 3.0, 4.0],
+    vec![1f64, 2.0, 3.0, 4.0],
+    0.06f64,
+    0.0f64,
+    norm = Norm::Mean
+);
+ok_test_ulps!(
+    compare_ulps_with_norm,
+    vec![1f64, 1.0 + 8.0 * f64::EPSILON, 1.0, 1.0],
+    vec![1f64, 1.0, 1.0, 1.0],
+    2u64,
+    norm = Norm::Mean
+);
+
+#[test]
+fn assert_appro_ne_with_norm() {
+    let actual = vec![1.1f64, 2.0, 3.0, 4.0];
+    let expected = vec![1f64, 2.0, 3.0, 4.0];
+    assert_appro_ne!(actual, expected, 0.06);
+    assert_appro_ne!(actual, expected, 0.04, norm = Norm::Rms);
+    assert_appro_ne_abs!(actual, expected, 0.02, norm = Norm::Mean);
+    assert_appro_ne_rel!(actual, expected, 0.02, norm = Norm::Mean);
+}
+
+#[test]
+fn norms() {
+    let actual = vec![1.3f64, 2.0, 3.4, 4.0];
+    let expected = vec![1f64, 2.0, 3.0, 4.0];
+    let error = |norm: Norm| {
+        AbsError::<_, f64>::abs_error(&norm.of(&actual), &expected)
+            .unwrap()
+            .unwrap()
+    };
+    assert_appro_eq!(error(Norm::Max), 0.4);
+    assert_appro_eq!(error(Norm::Mean), 0.7 / 4.0);
+    assert_appro_eq!(error(Norm::Rms), (0.25f64 / 4.0).sqrt());
+    assert_appro_eq!(error(Norm::L1), 0.7);
+    assert_appro_eq!(error(Norm::L2), 0.5);
+    assert_appro_eq!(error(Norm::Percentile(50.0)), 0.0);
+    assert_appro_eq!(error(Norm::Percentile(75.0)), 0.3);
+    assert_appro_eq!(error(Norm::Percentile(100.0)), 0.4);
+    assert_appro_eq!(error(Norm::Percentile(0.0)), 0.0);
+    assert_eq!(Norm::default(), Norm::Max);
+
+    let empty: Vec<f64> = Vec::new();
+    assert_eq!(
+        AbsError::<_, f64>::abs_error(&Norm::Rms.of(&empty), &empty).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn norm_report_path() {
+    let expected: Vec<f64> = (0..10).map(f64::from).collect();
+    let actual: Vec<f64> = (0..10).map(|i| f64::from(i) * 1.1).collect();
+    let mismatch =
+        abs_appro_check(&Norm::Percentile(90.0).of(&actual), &expected, &0.5).unwrap_err();
+    assert_appro_eq!(mismatch.error.unwrap(), 0.8);
+    assert_eq!(mismatch.path, vec![Segment::Index(8)]);
+
+    let mismatch = abs_appro_check(&Norm::Mean.of(&actual), &expected, &0.35).unwrap_err();
+    assert_appro_eq!(mismatch.error.unwrap(), 0.45);
+    assert!(mismatch.path.is_empty());
+    assert_eq!(mismatch.failing, 1);
+}
+
+#[test]
+fn norm_of_nested_and_other_collections() {
+    let actual = vec![vec![1.2f64, 2.0], vec![3.0, 4.4]];
+    let expected = vec![vec![1f64, 2.0], vec![3.0, 4.0]];
+    let error = AbsError::<_, f64>::abs_error(&Norm::Mean.of(&actual), &expected)
+        .unwrap()
+        .unwrap();
+    assert_appro_eq!(error, 0.15);
+
+    let actual = BTreeMap::from([("a", 1.2f64), ("b", 2.0)]);
+    let expected = BTreeMap::from([("a", 1f64), ("b", 2.0)]);
+    assert_appro_eq!(actual, expected, 0.11, norm = Norm::Mean);
+
+    let actual = VecDeque::from([1.2f64, 2.0]);
+    let expected = [1f64, 2.0];
+    assert_appro_eq!(actual, expected, 0.11, norm = Norm::Mean);
+
+    // The fields of tuples are reduced with the elements of the collections holding them.
+    assert_appro_eq!((1.2f64, 2.0f64), (1f64, 2f64), 0.11, norm = Norm::Mean);
+    let actual = vec![(1.2f64, 2.0f64), (3.0, 4.0)];
+    let expected = vec![(1f64, 2f64), (3.0, 4.0)];
+    assert_appro_eq!(actual, expected, 0.06, norm = Norm::Mean);
+}
+
+#[test]
+fn norm_of_integers_and_durations() {
+    let error = AbsError::<_, i32>::abs_error(&Norm::Mean.of(&vec![1i32, 4]), &vec![0i32, 2]);
+    assert_eq!(error.unwrap(), Some(2));
+    let error = AbsError::<_, Duration>::abs_error(
+        &Norm::Mean.of(&vec![Duration::from_millis(10), Duration::from_millis(30)]),
+        &vec![Duration::ZERO, Duration::ZERO],
+    );
+    assert_eq!(error.unwrap(), Some(Duration::from_millis(20)));
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+struct Level(u8);
+
+impl AbsError<Level, Level> for Level {
+    fn abs_error(&self, expected: &Level) -> Result<Option<Level>, ApproEqError> {
+        Ok(Some(Level(self.0.abs_diff(expected.0))))
+    }
+}
+
+impl Magnitude for Level {
+    fn to_f64(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    fn from_f64(value: f64) -> Result<Level, ApproEqError> {
+        u8::from_f64(value).map(Level)
+    }
+}
+
+#[test]
+fn norm_of_errors_with_magnitude() {
+    let actual = vec![Level(1), Level(3), Level(4)];
+    let expected = vec![Level(1), Level(2), Level(2)];
+    let error = Norm::Rms.of(&actual).abs_error(&expected);
+    assert_eq!(error.unwrap(), Some(Level(1)));
+    let error = Norm::Percentile(50.0).of(&actual).abs_error(&expected);
+    assert_eq!(error.unwrap(), Some(Level(1)));
+    let error = Norm::L1
+        .of(&vec![Level(255), Level(255)])
+        .abs_error(&vec![Level(0), Level(0)]);
+    assert!(matches!(error, Err(ApproEqError::Overflow)));
+}
+
+/// A reading whose error is NaN when either value is, unlike `f64` under the default `FloatPolicy`.
+#[derive(Debug)]
+struct Reading(f64);
+
+impl AbsError<Reading, f64> for Reading {
+    fn abs_error(&self, expected: &Reading) -> Result<Option<f64>, ApproEqError> {
+        Ok(Some((self.0 - expected.0).abs()))
+    }
+}
+
+#[test]
+fn percentile_orders_nan_last() {
+    let actual = vec![Reading(f64::NAN), Reading(1.0), Reading(2.5)];
+    let expected = vec![Reading(0.0), Reading(1.0), Reading(2.0)];
+    let error = Norm::Percentile(50.0).of(&actual).abs_error(&expected);
+    assert_eq!(error.unwrap(), Some(0.5));
+    let error = Norm::Percentile(100.0).of(&actual).abs_error(&expected);
+    assert!(error.unwrap().unwrap().is_nan());
+}
+
+fn with_outliers(at: &[usize]) -> (Vec<f64>, Vec<f64>) {
+    let expected = vec![1f64; 20];
+    let mut actual = expected.clone();
+    for &i in at {
+        actual[i] = 1.1;
+    }
+    (actual, expected)
+}
+
+#[test]
+fn outliers_within_count() {
+    let (actual, expected) = with_outliers(&[3, 11]);
+    assert_appro_eq!(actual, expected, 1e-6, outliers = Outliers::count(2));
+    assert_appro_eq_abs!(
+        actual,
+        expected,
+        1e-6,
+        outliers = Outliers::count(2).cap(0.2)
+    );
+    assert_appro_eq_rel!(actual, expected, 1e-6, outliers = Outliers::fraction(0.1));
+    assert_appro_eq_mixed!(
+        actual,
+        expected,
+        1e-6,
+        1e-6,
+        outliers = Outliers::count(2),
+        msg = "seed: {}",
+        42
+    );
+    assert_appro_ne!(actual, expected, 1e-6, outliers = Outliers::count(1));
+
+    let report = abs_appro_check(&Outliers::count(2).of(&actual), &expected, &1e-6).unwrap();
+    assert_eq!(report.path, vec![Segment::Index(3)]);
+    assert_eq!(
+        report.outliers,
+        vec![vec![Segment::Index(3)], vec![Segment::Index(11)]]
+    );
+}
+
+#[test]
+fn too_many_outliers() {
+    let (actual, expected) = with_outliers(&[1, 4, 7]);
+    let mismatch = abs_appro_check(&Outliers::count(2).of(&actual), &expected, &1e-6).unwrap_err();
+    match mismatch.cause {
+        Some(ApproEqError::TooManyOutliers {
+            allowed,
+            ref outliers,
+        }) => {
+            assert_eq!(allowed, 2);
+            assert_eq!(outliers.len(), 3);
+        }
+        ref cause => panic!("unexpected cause {:?}", cause),
+    }
+    assert_eq!(mismatch.failing, 3);
+    let message = mismatch.to_string();
+    assert!(message.starts_with(
+        "too many outliers (3 elements exceed the tolerance, 2 allowed: [1], [4], [7])"
+    ));
+    assert!(message.ends_with(" at [1]"));
+
+    let message = panic_message(|| {
+        let (actual, expected) = with_outliers(&(0..12).collect::<Vec<_>>());
+        assert_appro_eq!(actual, expected, 1e-6, outliers = Outliers::fraction(0.5));
+    });
+    assert!(
+        message.contains("too many outliers (12 elements exceed the tolerance, 10 allowed: [0]")
+    );
+    assert!(message.contains("[9] and 2 more)"));
+}
+
+#[test]
+fn outlier_cap() {
+    let (mut actual, expected) = with_outliers(&[5]);
+    actual[9] = 2.0;
+    let mismatch =
+        abs_appro_check(&Outliers::count(2).cap(0.5).of(&actual), &expected, &1e-6).unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::OutlierCap(cap)) if cap == 0.5));
+    assert_eq!(mismatch.error, Some(1.0));
+    assert_eq!(mismatch.path, vec![Segment::Index(9)]);
+    assert!(mismatch
+        .to_string()
+        .starts_with("element exceeds outlier cap `0.5`, error `1.0` at [9]"));
+
+    let mismatch = abs_appro_check(
+        &Outliers::count(1).cap(0.5).of(&vec![Level(1), Level(3)]),
+        &vec![Level(1), Level(2)],
+        &Level(0),
+    )
+    .unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::OutlierCap(_))));
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+}
+
+#[test]
+fn outliers_of_nested_collections() {
+    let expected = vec![[1f64, 2.0, 3.0]; 4];
+    let mut actual = expected.clone();
+    actual[1][2] = 3.5;
+    actual[3][0] = 1.5;
+    let report = abs_appro_check(&Outliers::count(2).of(&actual), &expected, &0.1).unwrap();
+    assert_eq!(
+        report.outliers,
+        vec![
+            vec![Segment::Index(1), Segment::Index(2)],
+            vec![Segment::Index(3), Segment::Index(0)]
+        ]
+    );
+    assert_appro_ne!(actual, expected, 0.1, outliers = Outliers::fraction(0.1));
+    assert_appro_eq!(actual, expected, 0.1, outliers = Outliers::fraction(0.2));
+}
+
+#[test]
+fn outliers_of_scalars() {
+    assert_appro_ne!(1f64, 2f64, 0.1, outliers = Outliers::count(1));
+    assert_appro_eq!(1f64, 1.05f64, 0.1, outliers = Outliers::count(1));
+}
+
+#[test]
+fn outliers_apply_to_errors() {
+    let (actual, expected) = with_outliers(&[3, 11]);
+    let error = AbsError::<_, f64>::abs_error(&Outliers::count(2).of(&actual), &expected);
+    assert_eq!(error.unwrap(), Some(0.0));
+    let error = AbsError::<_, f64>::abs_error(&Outliers::count(1).of(&actual), &expected)
+        .unwrap()
+        .unwrap();
+    assert_appro_eq!(error, 0.1);
+    assert!(Outliers::count(2)
+        .of(&actual)
+        .abs_appro_eq_with_tol(&expected, &1e-6));
+    assert!(Outliers::count(1)
+        .of(&actual)
+        .abs_appro_ne_with_tol(&expected, &1e-6));
+
+    let error = AbsError::<_, f64>::abs_error(&Outliers::count(2).cap(0.05).of(&actual), &expected);
+    match error {
+        Err(ApproEqError::At { path, cause }) => {
+            assert_eq!(path, vec![Segment::Index(3)]);
+            assert!(matches!(*cause, ApproEqError::OutlierCap(cap) if cap == 0.05));
+        }
+        error => panic!("unexpected error {:?}", error),
+    }
+    let error = AbsError::<_, f64>::abs_error(&Outliers::count(1).of(&1.1f64), &1f64)
+        .unwrap()
+        .unwrap();
+    assert_appro_eq!(error, 0.1);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn outliers_of_ndarray() {
+    let expected = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    let actual = arr2(&[[1f64, 2.0], [3.5, 4.0]]);
+    assert_appro_eq!(actual, expected, 0.1, outliers = Outliers::count(1));
+    let report = abs_appro_check(&Outliers::count(1).of(&actual), &expected, &0.1).unwrap();
+    assert_eq!(report.outliers, vec![vec![Segment::Coord(vec![1, 0])]]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn norm_of_ndarray() {
+    let actual = arr2(&[[1.3f64, 2.0], [3.4, 4.0]]);
+    let expected = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    assert_appro_eq!(actual, expected, 0.51, norm = Norm::L2);
+    assert_appro_ne!(actual, expected, 0.49, norm = Norm::L2);
+    let mismatch =
+        abs_appro_check(&Norm::Percentile(100.0).of(&actual), &expected, &0.1).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 0])]);
+}
+
+#[test]
+fn per_element_tolerances() {
+    let expected = vec![101_325f64, 293.15, 0.5];
+    let actual = vec![101_330f64, 293.2, 0.5];
+    assert_appro_eq!(actual, expected, tols = vec![10.0, 0.1, 1e-9]);
+    assert_appro_eq!(
+        actual,
+        expected,
+        tols = vec![10.0, 0.1, 1e-9],
+        norm = Norm::Mean
+    );
+    assert_appro_eq!(
+        [1f64, 2.0],
+        [1.05f64, 2.5],
+        tols = [0.1, 1.0],
+        msg = "seed: {}",
+        42
+    );
+    assert_appro_ne!(actual, expected, tols = vec![10.0, 0.01, 1e-9]);
+    assert_appro_eq!(
+        actual,
+        expected,
+        tols = vec![10.0, 0.01, 1e-9],
+        outliers = Outliers::count(1)
+    );
+
+    let tols = vec![10.0, 0.01, 1e-9];
+    let mismatch = tols_appro_check(&actual, &expected, &tols).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+    assert_appro_eq!(mismatch.error.unwrap(), 5.0, 1e-9);
+    assert_eq!(mismatch.tol, 1.0);
+    let report = tols_appro_check(&actual[..], &expected[..], &[20.0, 0.1, 1e-9][..]).unwrap();
+    assert_eq!(report.path, vec![Segment::Index(1)]);
+}
+
+#[test]
+fn weighted_tolerance() {
+    let expected = vec![1f64, 2.0, 3.0];
+    let actual = vec![1.1f64, 2.1, 4.0];
+    let tol = Tol::new().abs(0.2);
+    assert_appro_eq!(
+        actual,
+        expected,
+        tols = Weighted::new(tol, vec![1.0, 1.5, 0.0])
+    );
+    assert_appro_ne!(
+        actual,
+        expected,
+        tols = Weighted::new(tol, vec![1.0, 2.5, 0.0])
+    );
+    let mismatch =
+        tols_appro_check(&actual, &expected, &Weighted::new(tol, [1.0, 3.0, 0.0])).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+    assert_appro_eq!(mismatch.error.unwrap(), 1.5, 1e-9);
+    assert_appro_eq!(
+        [1f64, 2.0],
+        [1.05f64, 2.1],
+        tols = Weighted::new(Tol::new().rel(0.1), [1.0, 1.0])
+    );
+}
+
+#[test]
+fn per_element_tolerances_shape_mismatch() {
+    let mismatch = tols_appro_check(&vec![1f64, 2.0], &vec![1f64, 2.0], &vec![0.1]).unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::ShapeMismatch { ref values, ref tols }) if *values == [2] && *tols == [1]
+    ));
+    assert_eq!(
+        mismatch.to_string(),
+        "shape mismatch (tolerances of shape [1] for values of shape [2])"
+    );
+    let mismatch = tols_appro_check(
+        &vec![1f64, 2.0],
+        &vec![1f64],
+        &Weighted::new(Tol::new().abs(0.1), vec![1.0, 1.0]),
+    )
+    .unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::LengthMismatch)));
+    assert_appro_ne!(vec![1f64, 2.0], vec![1f64, 2.0], tols = vec![0.1]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn per_element_tolerances_of_ndarray() {
+    let expected = arr2(&[[101_325f64, 293.15], [101_000.0, 300.0]]);
+    let actual = arr2(&[[101_330f64, 293.2], [101_000.0, 300.5]]);
+    let tols = arr2(&[[10f64, 0.1], [10.0, 0.1]]);
+    let mismatch = tols_appro_check(&actual, &expected, &tols).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 1])]);
+    assert_appro_eq!(actual, expected, tols = arr2(&[[10f64, 0.1], [10.0, 1.0]]));
+    assert_appro_eq!(
+        actual,
+        expected,
+        tols = Weighted::new(Tol::new().rel(1e-3), arr2(&[[1f64, 1.0], [1.0, 0.5]]))
+    );
+
+    assert_appro_eq!(actual, expected, tols = arr1(&[10f64, 1.0]));
+    let mismatch = tols_appro_check(&actual, &expected, &arr2(&[[10f64, 0.1]])).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 1])]);
+    let mismatch = tols_appro_check(&actual, &expected, &arr1(&[10f64, 0.1, 1.0])).unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::ShapeMismatch { ref values, ref tols }) if *values == [2, 2] && *tols == [3]
+    ));
+    let weights = arr1(&[1f64, 1.0, 1.0, 1.0])
+        .into_shape_with_order((4, 1))
+        .unwrap();
+    let mismatch = tols_appro_check(
+        &actual,
+        &expected,
+        &Weighted::new(Tol::new().abs(1.0), weights),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::ShapeMismatch { ref values, ref tols }) if *values == [2, 2] && *tols == [4, 1]
+    ));
+}
+
+ok_test_none!(
+    compare_with_tol_abs,
+    1f64,
+    1.5f64,
+    tol = Tol::new().abs(0.6)
+);
+ok_test_none!(
+    compare_with_tol_rel,
+    100f64,
+    101f64,
+    tol = Tol::new().rel(0.01)
+);
+ok_test_none!(
+    compare_with_tol_ulps,
+    1f64,
+    1f64 + 2.0 * f64::EPSILON,
+    tol = Tol::<f64>::new().ulps(2)
+);
+ok_test_none!(
+    compare_with_tol_default,
+    0f64,
+    1e-12f64,
+    tol = Tol::<f64>::new()
+);
+ok_test_none!(
+    compare_with_tol_fixture,
+    vec![0f64, 1.0, 1e6],
+    vec![1e-12f64, 1.0, 1e6 + 0.5],
+    tol = FIXTURE_TOL
+);
+ok_test_none!(
+    compare_with_tol_policy,
+    [1f32, f32::NAN],
+    [1f32, f32::NAN],
+    tol = Tol::new().abs(1e-6).policy(NAN_EQ)
+);
+ok_test_none!(
+    compare_with_tol_rel_def,
+    1f64,
+    -3f64,
+    tol = Tol::new().rel(1.34).rel_def(RelErrorDef::Max)
+);
+ok_test_none!(
+    compare_with_tol_i32,
+    100i32,
+    103i32,
+    tol = Tol::new().abs(3)
+);
+ok_test_none!(compare_with_tol_u8, 200u8, 180u8, tol = Tol::new().rel(1));
+ok_test_none!(
+    compare_with_tol_duration,
+    Duration::new(10, 0),
+    Duration::new(10, 5_000_000),
+    tol = Tol::new().rel(Duration::new(0, 1_000_000))
+);
+ok_test_none!(
+    compare_with_tol_option,
+    Some(Rc::new(1f64)),
+    Some(Rc::new(1f64 + 1e-10)),
+    tol = Tol::new().abs(1e-9)
+);
+panic_test_none!(
+    bad_compare_with_tol_abs,
+    1f64,
+    1.7f64,
+    tol = Tol::new().abs(0.6)
+);
+panic_test_none!(
+    bad_compare_with_tol_ulps,
+    1f64,
+    1f64 + 3.0 * f64::EPSILON,
+    tol = Tol::<f64>::new().ulps(2)
+);
+panic_test_none!(
+    bad_compare_with_tol_default,
+    0f64,
+    1e-10f64,
+    tol = Tol::<f64>::new()
+);
+panic_test_none!(
+    bad_compare_with_tol_fixture,
+    vec![0f64, 1.0, 1e6],
+    vec![1e-6f64, 1.0, 1e6],
+    tol = FIXTURE_TOL
+);
+panic_test_none!(
+    bad_len_compare_with_tol,
+    vec![0f64, 1.0],
+    vec![0f64, 1.0, 1e6],
+    tol = FIXTURE_TOL
+);
+panic_test_none!(
+    bad_compare_with_tol_nan,
+    [1f32, f32::NAN],
+    [1f32, f32::NAN],
+    tol = Tol::new().abs(1e-6)
+);
+panic_test_none!(
+    bad_compare_with_tol_duration,
+    Duration::new(10, 0),
+    Duration::new(10, 20_000_000),
+    tol = Tol::new().rel(Duration::new(0, 1_000_000))
+);
+
+#[test]
+fn with_tolerance_overrides_defaults() {
+    with_tolerance(Tol::new().abs(1e-6f64).rel(1e-4), || {
+        assert_eq!(f64::abs_tolerance(), 1e-6);
+        assert_eq!(f64::rel_tolerance(), 1e-4);
+        assert_appro_eq!(1f64, 1.0000001f64);
+        assert_appro_eq_abs!(vec![1f64, 2.0], vec![1.0000001f64, 2.0]);
+        assert_appro_eq_rel!(1000f64, 1000.01f64);
+        assert_appro_eq_mixed!(1000f64, 1000.01f64);
+        assert_appro_eq!(0f64, 1e-7f64, tol = Tol::<f64>::new());
+        assert!(1f32.appro_ne_abs(&1.0001f32));
+    });
+    assert_eq!(f64::abs_tolerance(), 1e-11);
+    assert_eq!(f64::rel_tolerance(), 1e-11);
+}
+
+#[test]
+fn with_tolerance_nests() {
+    with_tolerance(Tol::new().abs(1e-6f64).rel(1e-4), || {
+        with_tolerance(Tol::new().abs(1e-3f64), || {
+            assert_eq!(f64::abs_tolerance(), 1e-3);
+            assert_eq!(f64::rel_tolerance(), 1e-4);
+            assert_appro_eq!(1f64, 1.0005f64);
+        });
+        assert_eq!(f64::abs_tolerance(), 1e-6);
+        with_tolerance(Tol::new().abs(3i32), || {
+            assert_appro_eq!(10i32, 13i32);
+            assert_eq!(f64::abs_tolerance(), 1e-6);
+        });
+        assert_eq!(i32::abs_tolerance(), 0);
+    });
+}
+
+#[test]
+fn with_tolerance_asks_for_the_tolerance_named() {
+    with_tolerance(Tol::new().abs(1e-3f64).rel(1e-4), || {
+        assert_eq!(f64::tolerance(), 1e-11);
+        assert_eq!(f64::abs_tolerance(), 1e-3);
+        assert_eq!(f64::tolerance(), 1e-11);
+        assert_eq!(f64::rel_tolerance(), 1e-4);
+    });
+}
+
+#[test]
+fn with_tolerance_is_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        with_tolerance(Tol::new().abs(1e-6f64).policy(NAN_EQ), || {
+            assert_appro_eq!(f64::NAN, f64::NAN);
+            assert_appro_eq!(1f64, 2f64);
+        });
+    });
+    assert!(result.is_err());
+    assert_eq!(f64::abs_tolerance(), 1e-11);
+    assert_eq!(FloatPolicy::current(), FloatPolicy::default());
+}
+
+#[test]
+fn with_tolerance_overrides_duration() {
+    with_tolerance(Tol::new().rel(Duration::new(0, 10_000_000)), || {
+        assert_appro_eq_rel!(
+            Duration::new(10, 0),
+            Duration::new(10, 50_000_000),
+            <Duration as RelTolerance>::rel_tolerance()
+        );
+        assert_eq!(Duration::abs_tolerance(), Duration::new(1, 0));
+    });
+    assert!(RelApproEq::<Duration, Duration>::rel_appro_ne(
+        &Duration::new(10, 0),
+        &Duration::new(10, 50_000_000)
+    ));
+}
+
+#[test]
+fn with_tolerance_overrides_ulps() {
+    with_tolerance(Tol::<f64>::new().ulps(8), || {
+        assert_appro_eq_ulps!(1f64, 1f64 + 8.0 * f64::EPSILON);
+        assert!(1f32.ulps_appro_ne(&(1f32 + 8.0 * f32::EPSILON)));
+    });
+    with_tolerance(Tol::<f32>::new().ulps(u64::MAX), || {
+        assert_appro_eq_ulps!(1f32, 2f32);
+    });
+    assert!(1f64.ulps_appro_ne(&(1f64 + 8.0 * f64::EPSILON)));
+}
+
+/// A tolerance that is neither `Clone` nor `'static`.
+#[derive(PartialEq, PartialOrd)]
+struct Slack<'a>(&'a f64);
+
+impl<'a> appro_eq::Tolerance for Slack<'a> {
+    fn tolerance() -> Slack<'a> {
+        Slack(&0.5)
+    }
+}
+
+#[test]
+fn tolerance_of_borrowed_values() {
+    assert!(Slack::abs_tolerance() == Slack(&0.5));
+    assert!(Slack::rel_tolerance() == Slack(&0.5));
+}
+
+#[test]
+#[should_panic]
+fn with_tolerance_does_not_leak() {
+    with_tolerance(Tol::new().abs(1e-6f64), || {});
+    assert_appro_eq!(1f64, 1.0000001f64);
+}
+
+#[test]
+fn tol_appro_ne() {
+    assert!(1f64.tol_appro_ne(&2f64, &Tol::new().abs(0.5)));
+    assert!(!1f64.tol_appro_ne(&2f64, &Tol::new().abs(0.5).rel(1.0)));
+}
+
 macro_rules! type_unsigned_impls {
     ($($T:ident)+) => {
         $(
@@ -323,8 +1536,101 @@ macro_rules! type_signed_impls {
     }
 }
 
-type_signed_impls! { i8 i16 i32 i64 i128 }
-type_unsigned_impls! { u8 u16 u32 u64 u128 }
+type_signed_impls! { i8 i16 i32 i64 i128 isize }
+type_unsigned_impls! { u8 u16 u32 u64 u128 usize }
+
+panic_test_all!(bad_compare_with_i8_extremes, i8::MIN, i8::MAX, 0i8);
+ok_test_all!(compare_with_i64_min, i64::MIN, i64::MIN, 0i64);
+
+#[test]
+fn integer_rel_error_ratio() {
+    assert_eq!(
+        RelError::<u32, u32>::rel_error(&1000, &999).unwrap(),
+        Some(1)
+    );
+    assert_eq!(
+        RelError::<u32, u32>::rel_error(&1000, &1000).unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        RelError::<u32, f64>::rel_error(&1000, &800).unwrap(),
+        Some(0.25)
+    );
+    assert_eq!(
+        RelError::<i16, f32>::rel_error_with_def(&-300, &100, RelErrorDef::Mean).unwrap(),
+        Some(2.0)
+    );
+    assert!(matches!(
+        RelError::<u8, f64>::rel_error(&1, &0),
+        Err(ApproEqError::DividedByZero)
+    ));
+    assert_appro_eq_rel!(1000u32, 1000u32);
+    assert_appro_ne_rel!(1000u32, 999u32);
+    assert_appro_ne_rel!(1000u32, 999u32, 0u32);
+    assert_appro_eq_rel!(1000u32, 999u32, 0.01);
+    assert_appro_ne_rel!(1000u32, 999u32, 0.0);
+    assert_appro_ne_rel!(1000u32, 999u32, 0.0f32);
+    assert_appro_eq_rel!(u128::MAX, u128::MAX);
+    assert_appro_eq_rel!(vec![100i64, -200], vec![99i64, -198], 0.011);
+    assert_appro_ne_rel!(vec![100i64, -200], vec![99i64, -198]);
+}
+
+#[test]
+fn integer_errors_do_not_overflow() {
+    assert!(matches!(
+        i8::MIN.abs_error(&i8::MAX),
+        Err(ApproEqError::Overflow)
+    ));
+    assert!(matches!(i8::MIN.abs_error(&0), Err(ApproEqError::Overflow)));
+    assert_eq!(i8::MIN.abs_error(&-1).unwrap(), Some(127));
+    assert_eq!(
+        RelError::<i8, i8>::rel_error(&i8::MIN, &i8::MAX).unwrap(),
+        Some(3)
+    );
+    assert!(matches!(
+        RelError::<i8, i8>::rel_error_with_def(&i8::MIN, &1, RelErrorDef::Min),
+        Err(ApproEqError::Overflow)
+    ));
+    assert_eq!(
+        RelError::<u8, u8>::rel_error_with_def(&1, &0, RelErrorDef::Mean).unwrap(),
+        Some(2)
+    );
+    assert_eq!(
+        RelError::<u8, u8>::rel_error_with_def(&u8::MAX, &(u8::MAX - 1), RelErrorDef::Mean)
+            .unwrap(),
+        Some(1)
+    );
+    assert_eq!(
+        RelError::<i8, i8>::rel_error_with_def(&i8::MIN, &i8::MAX, RelErrorDef::Mean).unwrap(),
+        Some(2)
+    );
+    assert!(matches!(
+        RelError::<u8, u8>::rel_error_with_def(&0, &0, RelErrorDef::Mean),
+        Err(ApproEqError::DividedByZero)
+    ));
+    assert_eq!(isize::MIN.abs_error(&isize::MIN).unwrap(), Some(0));
+    assert_eq!(u8::MAX.abs_error(&0).unwrap(), Some(255));
+    assert_appro_eq!(5usize, 7usize, 2usize);
+    assert_appro_eq!(-5isize, -7isize, 2isize);
+}
+
+#[test]
+fn ulps_of_large_differences_overflow() {
+    assert!(matches!(
+        appro_eq::TolError::tol_error(&0u128, &u128::MAX, &Tol::new().ulps(1)),
+        Err(ApproEqError::Overflow)
+    ));
+    assert!(matches!(
+        appro_eq::TolError::tol_error(&i128::MIN, &0i128, &Tol::new().ulps(1)),
+        Err(ApproEqError::Overflow)
+    ));
+    assert!(matches!(
+        appro_eq::TolError::tol_error(&Duration::ZERO, &Duration::MAX, &Tol::new().ulps(1)),
+        Err(ApproEqError::Overflow)
+    ));
+    let error = appro_eq::TolError::tol_error(&0u128, &u128::from(u64::MAX), &Tol::new().ulps(1));
+    assert_eq!(error.unwrap(), Some(u64::MAX as f64));
+}
 
 ok_test_all!(
     compare_with_option_both_some,
@@ -377,6 +1683,23 @@ panic_test_all!(
     Rational::new(1, 1000000000)
 );
 
+#[cfg(feature = "num-rational")]
+ok_test_rel!(
+    compare_with_ratio_rel_def,
+    Rational::new(-1, 2),
+    Rational::new(-1, 1),
+    Rational::new(1, 2),
+    RelErrorDef::Max
+);
+
+#[cfg(feature = "num-rational")]
+panic_test_rel!(
+    bad_compare_with_ratio_rel_negative,
+    Rational::new(-1, 2),
+    Rational::new(-1, 1),
+    Rational::new(1, 3)
+);
+
 ok_test_all!(compare_with_rc, Rc::new(1.0), Rc::new(1.0));
 panic_test_all!(bad_compare_with_rc, Rc::new(1.0), Rc::new(1.00001));
 
@@ -404,6 +1727,201 @@ panic_test_all!(
     RefCell::new(1.00001)
 );
 
+ok_test_all!(compare_with_box, Box::new(1.0), Box::new(1.0));
+panic_test_all!(bad_compare_with_box, Box::new(1.0), Box::new(1.00001));
+
+ok_test_all!(
+    compare_with_boxed_slice,
+    vec![1f64, 2.0].into_boxed_slice(),
+    vec![1f64, 2.0].into_boxed_slice()
+);
+ok_test_all!(
+    compare_boxed_slice_with_vec,
+    vec![1f64, 2.0].into_boxed_slice(),
+    vec![1f64, 2.0]
+);
+ok_test_all!(
+    compare_vec_with_boxed_slice,
+    vec![1f64, 2.0],
+    vec![1f64, 2.0].into_boxed_slice()
+);
+ok_test_all!(
+    compare_boxed_slice_with_array,
+    vec![1f64, 2.0].into_boxed_slice(),
+    [1f64, 2.0]
+);
+ok_test_all!(
+    compare_array_with_boxed_slice,
+    [1f64, 2.0],
+    vec![1f64, 2.0].into_boxed_slice()
+);
+panic_test_all!(
+    bad_compare_boxed_slice_with_vec,
+    vec![1f64, 2.0].into_boxed_slice(),
+    vec![1f64, 2.00001]
+);
+panic_test_all!(
+    bad_compare_boxed_slice_with_array_len,
+    vec![1f64, 2.0].into_boxed_slice(),
+    [1f64, 2.0, 3.0]
+);
+
+ok_test_all!(compare_with_array64, [1f64; 64], [1f64; 64]);
+ok_test_all!(compare_with_array256, [1f32; 256], [1f32; 256]);
+panic_test_all!(bad_compare_with_array64, [1f64; 64], [1.00001f64; 64]);
+
+ok_test_all!(
+    compare_with_vecdeque,
+    VecDeque::from(vec![1f64, 2.0]),
+    VecDeque::from(vec![1f64, 2.0])
+);
+ok_test_all!(
+    compare_vecdeque_with_array,
+    VecDeque::from(vec![1f64, 2.0]),
+    [1f64, 2.0]
+);
+ok_test_all!(
+    compare_slice_with_vecdeque,
+    &[1f64, 2.0][..],
+    VecDeque::from(vec![1f64, 2.0])
+);
+ok_test_all!(compare_vec_with_slice, vec![1f64, 2.0], &[1f64, 2.0][..]);
+ok_test_all!(compare_slice_with_vec, &[1f64, 2.0][..], vec![1f64, 2.0]);
+ok_test_all!(compare_vec_with_array, vec![1f64, 2.0], [1f64, 2.0]);
+ok_test_all!(compare_array_with_vec, [1f64, 2.0], vec![1f64, 2.0]);
+ok_test_all!(
+    compare_boxed_slice_with_slice,
+    vec![1f64, 2.0].into_boxed_slice(),
+    &[1f64, 2.0][..]
+);
+panic_test_all!(
+    bad_compare_vecdeque_with_vec,
+    VecDeque::from(vec![1f64, 2.0]),
+    vec![1f64, 2.00001]
+);
+panic_test_all!(
+    bad_compare_vec_with_array_len,
+    vec![1f64, 2.0],
+    [1f64, 2.0, 3.0]
+);
+
+ok_test_all!(
+    compare_with_cow,
+    Cow::<[f64]>::Borrowed(&[1.0, 2.0]),
+    Cow::<[f64]>::Owned(vec![1.0, 2.0])
+);
+panic_test_all!(
+    bad_compare_with_cow,
+    Cow::<[f64]>::Borrowed(&[1.0, 2.0]),
+    Cow::<[f64]>::Owned(vec![1.0, 2.00001])
+);
+
+ok_test_all!(compare_with_mutex, Mutex::new(1.0), Mutex::new(1.0));
+panic_test_all!(bad_compare_with_mutex, Mutex::new(1.0), Mutex::new(1.00001));
+
+ok_test_all!(compare_with_rwlock, RwLock::new(1.0), RwLock::new(1.0));
+panic_test_all!(
+    bad_compare_with_rwlock,
+    RwLock::new(1.0),
+    RwLock::new(1.00001)
+);
+
+ok_test_all!(compare_with_pin, Box::pin(1.0), Box::pin(1.0));
+panic_test_all!(bad_compare_with_pin, Box::pin(1.0), Box::pin(1.00001));
+
+ok_test_all!(compare_with_mut_ref, &mut 1.0, &mut 1.0);
+panic_test_all!(bad_compare_with_mut_ref, &mut 1.0, &mut 1.00001);
+
+#[test]
+fn compare_poisoned_lock() {
+    let poisoned = Arc::new(Mutex::new(1f64));
+    let lock = poisoned.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = lock.lock().unwrap();
+        panic!("poison");
+    })
+    .join();
+    let mismatch = abs_appro_check(&*poisoned, &Mutex::new(1f64), &1.0).unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::Poisoned)));
+
+    let rwlock = RwLock::new(1f64);
+    let report = abs_appro_check(&rwlock, &rwlock, &1.0).unwrap();
+    assert_eq!(report.error, Some(0.0));
+    let nan = RwLock::new(f64::NAN);
+    assert!(abs_appro_check(&nan, &nan, &1.0).is_err());
+
+    let mutex = Mutex::new(1f64);
+    let mismatch = abs_appro_check(&mutex, &mutex, &1.0).unwrap_err();
+    assert!(matches!(mismatch.cause, Some(ApproEqError::Locked)));
+}
+
+#[test]
+fn compare_locks_from_two_threads() {
+    let a = Arc::new(Mutex::new(vec![1f64; 16]));
+    let b = Arc::new(Mutex::new(vec![1f64; 16]));
+    let c = Arc::new(RwLock::new(vec![1f64; 16]));
+    let d = Arc::new(RwLock::new(vec![1f64; 16]));
+    let threads: Vec<_> = [false, true]
+        .iter()
+        .map(|&reversed| {
+            let (a, b, c, d) = (a.clone(), b.clone(), c.clone(), d.clone());
+            std::thread::spawn(move || {
+                for _ in 0..10_000 {
+                    if reversed {
+                        assert_appro_eq!(*b, *a);
+                        assert_appro_eq!(*d, *c);
+                        *d.write().unwrap() = vec![1f64; 16];
+                    } else {
+                        assert_appro_eq!(*a, *b);
+                        assert_appro_eq!(*c, *d);
+                        *c.write().unwrap() = vec![1f64; 16];
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+ok_test_all!(
+    compare_with_tuple,
+    (1f64, vec![2f64, 3.0], [4f64; 2]),
+    (1f64, vec![2f64, 3.0], [4f64 + 1e-12; 2])
+);
+ok_test_all!(
+    compare_with_tuple12,
+    (1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32),
+    (1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32)
+);
+panic_test_all!(
+    bad_compare_with_tuple,
+    (1f64, vec![2f64, 3.0]),
+    (1f64, vec![2f64, 4.0])
+);
+
+ok_test_all!(
+    compare_with_btreemap,
+    BTreeMap::from([("a", 1f64), ("b", 2.0)]),
+    BTreeMap::from([("b", 2f64), ("a", 1.0 + 1e-12)])
+);
+ok_test_all!(
+    compare_with_hashmap,
+    HashMap::from([(1u8, vec![1f64, 2.0]), (2, vec![3.0])]),
+    HashMap::from([(2u8, vec![3f64]), (1, vec![1.0, 2.0])])
+);
+panic_test_all!(
+    bad_compare_with_btreemap_val,
+    BTreeMap::from([("a", 1f64), ("b", 2.0)]),
+    BTreeMap::from([("a", 1f64), ("b", 3.0)])
+);
+panic_test_all!(
+    bad_compare_with_hashmap_keys,
+    HashMap::from([("a", 1f64), ("b", 2.0)]),
+    HashMap::from([("a", 1f64), ("c", 2.0)])
+);
+
 #[cfg(feature = "ndarray")]
 ok_test_all!(
     compare_with_ndarray1d,
@@ -532,3 +2050,670 @@ panic_test_all!(
     Duration::new(9, 0),
     Duration::new(10, 1)
 );
+
+ok_test_rel!(
+    compare_with_duration_rel_tolerance,
+    Duration::new(10, 0),
+    Duration::new(10, 1),
+    <Duration as RelTolerance>::rel_tolerance()
+);
+ok_test_rel!(
+    compare_with_duration_ratio,
+    Duration::new(10, 0),
+    Duration::new(10, 1),
+    1e-9
+);
+panic_test_rel!(
+    bad_compare_with_duration_rel_tolerance,
+    Duration::new(9, 0),
+    Duration::new(10, 1),
+    <Duration as RelTolerance>::rel_tolerance()
+);
+panic_test_rel!(
+    bad_compare_with_duration_ratio,
+    Duration::new(9, 0),
+    Duration::new(10, 1),
+    0.05
+);
+
+ok_test_rel!(
+    compare_with_duration_under_one_second,
+    Duration::from_millis(500),
+    Duration::from_millis(510),
+    0.02
+);
+panic_test_rel!(
+    bad_compare_with_duration_under_one_second,
+    Duration::from_millis(500),
+    Duration::from_millis(510),
+    Duration::from_millis(10)
+);
+
+#[test]
+fn duration_rel_error() {
+    assert_eq!(
+        RelError::<Duration, f64>::rel_error(
+            &Duration::from_millis(550),
+            &Duration::from_millis(500)
+        )
+        .unwrap(),
+        Some(0.1)
+    );
+    assert_eq!(
+        RelError::<Duration, Duration>::rel_error(
+            &Duration::from_millis(550),
+            &Duration::from_millis(500)
+        )
+        .unwrap(),
+        Some(Duration::from_millis(100))
+    );
+    assert!(matches!(
+        RelError::<Duration, f64>::rel_error(&Duration::from_millis(1), &Duration::ZERO),
+        Err(ApproEqError::DividedByZero)
+    ));
+    assert_eq!(
+        <Duration as RelTolerance>::rel_tolerance(),
+        Duration::from_millis(1)
+    );
+    assert_eq!(<Duration as RelTolerance<f64>>::rel_tolerance(), 0.001);
+}
+
+#[test]
+fn compare_elapsed_with_duration() {
+    let start = Instant::now();
+    let fired = Elapsed::new(start, start + Duration::from_millis(1020));
+    assert_appro_eq_rel!(fired, Duration::from_secs(1), 0.05);
+    assert_appro_eq_rel!(fired, Duration::from_secs(1), Duration::from_millis(50));
+    assert_appro_eq_abs!(fired, Duration::from_secs(1), Duration::from_millis(50));
+    assert_appro_eq!(
+        fired,
+        Duration::from_secs(1),
+        tol = Tol::new().rel(Duration::from_millis(50))
+    );
+    assert_eq!(
+        RelError::<Duration, f64>::rel_error(&fired, &Duration::from_millis(1000)).unwrap(),
+        Some(0.02)
+    );
+
+    let start = SystemTime::now();
+    let fired = Elapsed::new(start, start + Duration::from_millis(1020));
+    assert_appro_eq_rel!(fired, Duration::from_secs(1), 0.05);
+}
+
+#[test]
+#[should_panic]
+fn bad_compare_elapsed_with_duration() {
+    let start = Instant::now();
+    let fired = Elapsed::new(start, start + Duration::from_millis(1100));
+    assert_appro_eq_rel!(fired, Duration::from_secs(1), 0.05);
+}
+
+#[test]
+fn compare_elapsed_instant_with_systemtime() {
+    let (instant, system) = (Instant::now(), SystemTime::now());
+    let a = Elapsed::new(instant, instant + Duration::from_millis(200));
+    let b = Elapsed::new(system, system + Duration::from_millis(202));
+    assert_appro_eq_rel!(a, b, 0.02);
+    assert_appro_eq_rel!(b, a, 0.02);
+    assert_appro_eq_abs!(a, b, Duration::from_millis(5));
+    assert_appro_ne!(a, b, Duration::from_millis(1));
+    assert_appro_eq_rel!(
+        a,
+        Elapsed::new(instant, instant + Duration::from_millis(199)),
+        0.01
+    );
+}
+
+#[test]
+fn elapsed_before_origin() {
+    let system = SystemTime::now();
+    let early = Elapsed::new(system, system - Duration::from_secs(2));
+    match RelError::<Duration, f64>::rel_error(&early, &Duration::from_secs(1)) {
+        Err(ApproEqError::BeforeOrigin(by)) => assert_eq!(by, Duration::from_secs(2)),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(
+        ApproEqError::BeforeOrigin(Duration::from_secs(2)).to_string(),
+        "time 2s before origin"
+    );
+
+    let instant = Instant::now() + Duration::from_secs(10);
+    let early = Elapsed::new(instant, instant - Duration::from_secs(3));
+    assert!(
+        matches!(early.duration(), Err(ApproEqError::BeforeOrigin(by)) if by == Duration::from_secs(3))
+    );
+}
+
+#[test]
+fn compare_systemtime_with_unix_duration() {
+    let now = SystemTime::now();
+    let since_epoch = now.duration_since(UNIX_EPOCH).unwrap();
+    assert_appro_eq_abs!(
+        now,
+        since_epoch + Duration::from_millis(10),
+        Duration::from_millis(20)
+    );
+    assert_appro_eq_abs!(since_epoch, now, Duration::from_millis(1));
+    assert_appro_ne!(
+        now,
+        since_epoch + Duration::from_secs(10),
+        Duration::from_secs(1)
+    );
+    assert!(matches!(
+        AbsError::<Duration, Duration>::abs_error(&now, &Duration::MAX),
+        Err(ApproEqError::Overflow)
+    ));
+}
+
+#[cfg(feature = "ndarray")]
+ok_test_ulps!(
+    compare_ulps_with_ndarray2d,
+    arr2(&[[1f64, 2.0], [3.0, 4.0]]),
+    arr2(&[[1f64, 2.0], [3.0, 4.0 + 4.0 * f64::EPSILON]])
+);
+
+#[cfg(feature = "ndarray")]
+panic_test_ulps!(
+    bad_compare_ulps_with_ndarray2d,
+    arr2(&[[1f64, 2.0], [3.0, 4.0]]),
+    arr2(&[[1f64, 2.0], [3.0, 4.0001]])
+);
+
+#[cfg(feature = "ndarray")]
+ok_test_mixed!(
+    compare_mixed_with_ndarray2d,
+    arr2(&[[0f64, 1.0], [1e6, -1e6]]),
+    arr2(&[[1e-12f64, 1.0], [1e6 + 0.5, -1e6]]),
+    1e-9f64,
+    1e-6f64
+);
+
+#[cfg(feature = "ndarray")]
+panic_test_mixed!(
+    bad_compare_mixed_with_ndarray2d,
+    arr2(&[[0f64, 1.0], [1e6, -1e6]]),
+    arr2(&[[1e-6f64, 1.0], [1e6, -1e6]]),
+    1e-9f64,
+    1e-6f64
+);
+
+#[cfg(feature = "ndarray")]
+ok_test_all!(
+    compare_with_nan_policy_ndarray2d,
+    arr2(&[[1f64, f64::NAN], [3.0, 4.0]]),
+    arr2(&[[1f64, f64::NAN], [3.0, 4.0]]),
+    policy = NAN_EQ
+);
+
+#[cfg(feature = "ndarray")]
+panic_test_all!(
+    bad_compare_with_nan_ndarray2d,
+    arr2(&[[1f64, f64::NAN], [3.0, 4.0]]),
+    arr2(&[[1f64, f64::NAN], [3.0, 4.0]])
+);
+
+#[cfg(feature = "ndarray")]
+ok_test_none!(
+    compare_with_tol_ndarray2d,
+    arr2(&[[0f64, 1.0], [1e6, -1e6]]),
+    arr2(&[[1e-12f64, 1.0], [1e6 + 0.5, -1e6]]),
+    tol = FIXTURE_TOL
+);
+
+#[cfg(feature = "ndarray")]
+panic_test_none!(
+    bad_compare_with_tol_ndarray2d,
+    arr2(&[[0f64, 1.0], [1e6, -1e6]]),
+    arr2(&[[1e-6f64, 1.0], [1e6, -1e6]]),
+    tol = FIXTURE_TOL
+);
+
+#[test]
+fn abs_appro_check_reports_worst_element() {
+    let report = abs_appro_check(&vec![1f64, 2.0, 3.0], &vec![1.1f64, 2.0, 2.8], &0.5).unwrap();
+    assert_appro_eq!(report.error.unwrap(), 0.2);
+    assert_eq!(report.tol, 0.5);
+    assert_eq!(report.path, vec![Segment::Index(2)]);
+
+    let mismatch = abs_appro_check(&[1f64, 2.0, 3.0], &[1f64, 2.5, 3.1], &0.2).unwrap_err();
+    assert_eq!(mismatch.error, Some(0.5));
+    assert_eq!(mismatch.tol, 0.2);
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+    assert!(mismatch.cause.is_none());
+    assert_eq!(
+        mismatch.to_string(),
+        "error `0.5` exceeds tolerance `0.2` at [1]"
+    );
+}
+
+#[test]
+fn abs_appro_check_reports_nested_path() {
+    let actual = vec![vec![1i32, 2], vec![3, 4, 5]];
+    let expected = vec![vec![1i32, 2], vec![3, 9, 5]];
+    let mismatch = abs_appro_check(&actual, &expected, &1).unwrap_err();
+    assert_eq!(mismatch.error, Some(5));
+    assert_eq!(mismatch.path, vec![Segment::Index(1), Segment::Index(1)]);
+    assert_eq!(
+        mismatch.to_string(),
+        "error `5` exceeds tolerance `1` at [1][1]"
+    );
+
+    let report = abs_appro_check(&Some(Rc::new(2i32)), &Some(Rc::new(3i32)), &1).unwrap();
+    assert_eq!(report.error, Some(1));
+    assert!(report.path.is_empty());
+}
+
+#[test]
+fn abs_appro_check_reports_tuple_position() {
+    let actual = (1f64, vec![2f64, 3.0], 4f64);
+    let expected = (1.5f64, vec![2f64, 5.0], 4f64);
+    let mismatch = abs_appro_check(&actual, &expected, &1.0).unwrap_err();
+    assert_eq!(mismatch.error, Some(2.0));
+    assert_eq!(mismatch.path, vec![Segment::Field("1"), Segment::Index(1)]);
+    assert_eq!(mismatch.failing, 1);
+    assert_eq!(
+        mismatch.to_string(),
+        "error `2.0` exceeds tolerance `1.0` at .1[1]"
+    );
+}
+
+#[test]
+fn abs_appro_check_reports_map_key() {
+    let actual = BTreeMap::from([("pressure".to_string(), 1f64), ("temp".to_string(), 20.0)]);
+    let expected = BTreeMap::from([("pressure".to_string(), 1f64), ("temp".to_string(), 21.5)]);
+    let mismatch = abs_appro_check(&actual, &expected, &1.0).unwrap_err();
+    assert_eq!(mismatch.error, Some(1.5));
+    assert_eq!(mismatch.path, vec![Segment::Key("\"temp\"".to_string())]);
+    assert_eq!(
+        mismatch.to_string(),
+        "error `1.5` exceeds tolerance `1.0` at [\"temp\"]"
+    );
+
+    let actual = HashMap::from([("a", 1f64), ("b", 2.0), ("d", 4.0)]);
+    let expected = HashMap::from([("a", 1f64), ("c", 2.0)]);
+    let mismatch = abs_appro_check(&actual, &expected, &1.0).unwrap_err();
+    match &mismatch.cause {
+        Some(ApproEqError::KeyMismatch { missing, extra }) => {
+            assert_eq!(missing, &vec!["\"c\"".to_string()]);
+            assert_eq!(extra, &vec!["\"b\"".to_string(), "\"d\"".to_string()]);
+        }
+        cause => panic!("unexpected cause {:?}", cause),
+    }
+    assert_eq!(
+        mismatch.to_string(),
+        "key mismatch (missing: \"c\"; extra: \"b\", \"d\")"
+    );
+}
+
+#[test]
+fn abs_appro_check_reports_cause() {
+    let actual = vec![vec![1f64], vec![2.0, 3.0]];
+    let expected = vec![vec![1f64], vec![2.0]];
+    let mismatch = abs_appro_check(&actual, &expected, &0.1).unwrap_err();
+    assert!(mismatch.error.is_none());
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+    match mismatch.cause {
+        Some(ApproEqError::LengthMismatch) => {}
+        ref cause => panic!("unexpected cause {:?}", cause),
+    }
+    assert_eq!(mismatch.to_string(), "length mismatch at [1]");
+    assert!(std::error::Error::source(&mismatch).is_some());
+
+    let mismatch = abs_appro_check(&[1f64, f64::NAN], &[1f64, 1.0], &0.1).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::NonNumDifference)
+    ));
+}
+
+#[test]
+fn error_reports_path() {
+    let actual = vec![vec![Some(1f64)], vec![Some(2.0), None]];
+    let expected = vec![vec![Some(1f64)], vec![Some(2.0), Some(3.0)]];
+    let error = AbsError::<_, f64>::abs_error(&actual, &expected).unwrap_err();
+    assert_eq!(error.path(), &[Segment::Index(1), Segment::Index(1)]);
+    assert!(matches!(error.inner(), ApproEqError::NonNumDifference));
+    assert_eq!(error.to_string(), "non num difference at [1][1]");
+    assert!(std::error::Error::source(&error).is_some());
+
+    let error =
+        AbsError::<_, f64>::abs_error(&vec![vec![1f64]], &vec![vec![1f64, 2.0]]).unwrap_err();
+    assert_eq!(error.to_string(), "length mismatch at [0]");
+    let error = AbsError::<_, f64>::abs_error(&vec![1f64], &vec![1f64, 2.0]).unwrap_err();
+    assert!(error.path().is_empty());
+    assert!(matches!(error.inner(), ApproEqError::LengthMismatch));
+
+    let actual = BTreeMap::from([("key", (1f64, vec![f64::NAN]))]);
+    let expected = BTreeMap::from([("key", (1f64, vec![1f64]))]);
+    let error = AbsError::<_, f64>::abs_error(&actual, &expected).unwrap_err();
+    assert_eq!(error.to_string(), "non num difference at [\"key\"].1[0]");
+}
+
+#[test]
+fn component_error_source() {
+    let error = ApproEqError::ComponentError(Box::new(ApproEqError::Overflow));
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(source.to_string(), "overflow");
+    assert!(std::error::Error::source(&ApproEqError::Overflow).is_none());
+}
+
+#[cfg(feature = "num-complex")]
+#[test]
+fn complex_error_reports_part() {
+    let actual = vec![Complex::new(1f64, 2.0), Complex::new(1.0, f64::NAN)];
+    let expected = vec![Complex::new(1f64, 2.0), Complex::new(1.0, 2.0)];
+    let error = AbsError::<_, f64>::abs_error(&actual, &expected).unwrap_err();
+    assert_eq!(error.to_string(), "non num difference at [1].im");
+    let mismatch = abs_appro_check(&actual, &expected, &0.1).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Index(1), Segment::Field("im")]);
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::NonNumDifference)
+    ));
+}
+
+#[test]
+fn other_appro_checks() {
+    let mismatch = rel_appro_check(&vec![100f64, 200.0], &vec![101f64, 200.0], &0.001).unwrap_err();
+    assert_appro_eq!(mismatch.error.unwrap(), 0.01 / 1.01);
+    assert_eq!(mismatch.path, vec![Segment::Index(0)]);
+    let report =
+        rel_appro_check_with_def(&[0f64, 1.0], &[1e-12f64, 1.0], &1.0, RelErrorDef::Max).unwrap();
+    assert_eq!(report.path, vec![Segment::Index(0)]);
+
+    let mismatch =
+        ulps_appro_check(&[1f64, 1.0], &[1f64, 1.0 + 8.0 * f64::EPSILON], &4u64).unwrap_err();
+    assert_eq!(mismatch.error, Some(8));
+    assert_eq!(mismatch.path, vec![Segment::Index(1)]);
+
+    let mismatch = mixed_appro_check(&[0f64, 1e6], &[1e-6f64, 1e6], &1e-9, &1e-6).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Index(0)]);
+    assert_eq!(mismatch.tol.abs, Some(1e-9));
+    assert_eq!(mismatch.tol.rel, Some(1e-6));
+
+    let report = tol_appro_check(
+        &[f64::NAN, 1.0],
+        &[f64::NAN, 1.5],
+        &Tol::new().abs(1.0).policy(NAN_EQ),
+    )
+    .unwrap();
+    assert_eq!(report.error, Some(0.5));
+    assert_eq!(report.path, vec![Segment::Index(1)]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn abs_appro_check_reports_ndarray_coord() {
+    let actual = arr2(&[[1f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let expected = arr2(&[[1f64, 2.0, 3.0], [4.0, 5.0, 6.5]]);
+    let mismatch = abs_appro_check(&actual, &expected, &0.1).unwrap_err();
+    assert_eq!(mismatch.error, Some(0.5));
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 2])]);
+    assert_eq!(
+        mismatch.to_string(),
+        "error `0.5` exceeds tolerance `0.1` at [1, 2]"
+    );
+
+    let actual = arr3(&[[[1f64], [2.0]], [[3.0], [4.0]]]).reversed_axes();
+    let expected = actual.mapv(|v| if v == 2.0 { 3.0 } else { v });
+    let report = abs_appro_check(&actual, &expected, &1.0).unwrap();
+    assert_eq!(report.path, vec![Segment::Coord(vec![0, 1, 0])]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn compare_ndarray_with_broadcast_row() {
+    let actual = arr2(&[[1f64, 2.0, 3.0], [1.0, 2.0, 3.05]]);
+    let row = arr1(&[1f64, 2.0, 3.0]);
+    assert_appro_eq!(actual, row, 0.1);
+    assert_appro_eq_rel!(actual, row, 0.02);
+    assert_appro_eq_mixed!(actual, row, 0.1, 0.0);
+    assert_appro_eq!(actual, row, tol = Tol::new().abs(0.1));
+    assert_appro_ne!(actual, row, 0.01);
+    let mismatch = abs_appro_check(&actual, &row, &0.01).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 2])]);
+
+    let column = arr2(&[[1f64], [2.0]]);
+    let report = abs_appro_check(&arr2(&[[1f64, 1.5], [2.0, 3.0]]), &column, &1.0).unwrap();
+    assert_eq!(report.error, Some(1.0));
+    assert_eq!(report.path, vec![Segment::Coord(vec![1, 1])]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn compare_ndarray_with_scalar_array() {
+    let actual = arr2(&[[1f64, 1.0], [1.0, 1.0 + 2.0 * f64::EPSILON]]);
+    assert_appro_eq!(actual, arr0(1f64));
+    assert_appro_eq_ulps!(actual, arr0(1f64), 2);
+    assert_appro_ne!(actual, arr0(2f64));
+    assert_appro_eq!(arr0(1f64), arr0(1f64));
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn compare_ndarray_of_different_dimension_types() {
+    let actual = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    let expected = ArrayD::from_shape_vec(IxDyn(&[2, 2]), vec![1f64, 2.0, 3.0, 4.5]).unwrap();
+    assert_appro_eq!(actual, expected, 0.6);
+    assert_appro_eq!(expected, actual, 0.6);
+    let mismatch = abs_appro_check(&actual, &expected, &0.1).unwrap_err();
+    assert_eq!(mismatch.path, vec![Segment::Coord(vec![1, 1])]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn bad_broadcast_reports_shapes() {
+    let actual = arr2(&[[1f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let mismatch = abs_appro_check(&actual, &arr1(&[1f64, 2.0]), &0.1).unwrap_err();
+    assert!(matches!(
+        mismatch.cause,
+        Some(ApproEqError::BroadcastMismatch { ref actual, ref expected })
+            if *actual == [2, 3] && *expected == [2]
+    ));
+    assert_eq!(
+        mismatch.to_string(),
+        "shape mismatch (expected shape [2] cannot be broadcast to actual shape [2, 3])"
+    );
+    assert_appro_ne!(actual, arr2(&[[1f64, 2.0, 3.0]]).reversed_axes());
+    let row = arr1(&[1f64, 2.0, 3.0]);
+    assert_appro_ne!(row, actual);
+    assert_appro_ne!(arr2(&[[1f64, 2.0, 3.0]]), actual);
+    assert_appro_ne!(arr0(1f64), arr1(&[1f64, 1.0]));
+}
+
+fn panic_message<F: FnOnce() + std::panic::UnwindSafe>(f: F) -> String {
+    let payload = std::panic::catch_unwind(f).unwrap_err();
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn assertion_message_reports_worst_element() {
+    let actual = vec![1f64; 10_000];
+    let mut expected = actual.clone();
+    expected[17] = 1.25;
+    expected[9_998] = 1.5;
+    expected[9_999] = 0.75;
+    let message = panic_message(|| assert_appro_eq!(actual, expected));
+    assert!(message.contains(
+        "error: error `0.5` exceeds default tolerance `1e-11` at [9998] (3 elements exceed the tolerance)"
+    ));
+    assert!(message.contains("more characters)"));
+    assert!(message.len() < 1_000);
+
+    let message = panic_message(|| assert_appro_eq_abs!(vec![1f64, 2.0], vec![1f64, 2.5], 0.1));
+    assert!(message.contains("  left: `[1.0, 2.0]`"));
+    assert!(message.contains(" right: `[1.0, 2.5]`"));
+    assert!(message.contains("error: error `0.5` exceeds tolerance `0.1` at [1]"));
+}
+
+#[test]
+fn assertion_message_names_errors() {
+    let message = panic_message(|| assert_appro_eq!(vec![1f64, 2.0], vec![1f64]));
+    assert!(message.contains("error: length mismatch"));
+    let message = panic_message(|| assert_appro_eq_rel!(vec![1i32, 1], vec![1i32, 0]));
+    assert!(message.contains("error: divided by zero at [1]"));
+    let message = panic_message(|| assert_appro_eq_ulps!(1f64, f64::NAN));
+    assert!(message.contains("error: non num difference"));
+}
+
+#[test]
+fn assertions_evaluate_operands_once() {
+    let count = std::sync::atomic::AtomicUsize::new(0);
+    let next = || {
+        count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        1f64
+    };
+    assert_appro_eq!(next(), 1f64);
+    assert_appro_eq_rel!(next(), 1f64);
+    assert_appro_ne!(next(), 2f64, 0.5);
+    panic_message(|| assert_appro_eq!(next(), 2f64));
+    panic_message(|| assert_appro_eq_rel!(next(), 2f64, msg = "seed: {}", 42));
+    panic_message(|| assert_appro_ne_abs!(next(), 1f64));
+    assert_eq!(count.into_inner(), 6);
+}
+
+#[test]
+fn mismatch_counts_failing_elements() {
+    let actual = vec![vec![1f64, 2.0], vec![3.0, 4.0, 5.0]];
+    let expected = vec![vec![1.5f64, 2.0], vec![3.0, 4.2, 6.0]];
+    let mismatch = abs_appro_check(&actual, &expected, &0.3).unwrap_err();
+    assert_eq!(mismatch.failing, 2);
+    assert_eq!(mismatch.path, vec![Segment::Index(1), Segment::Index(2)]);
+    assert_eq!(
+        mismatch.to_string(),
+        "error `1.0` exceeds tolerance `0.3` at [1][2] (2 elements exceed the tolerance)"
+    );
+    assert_eq!(abs_appro_check(&1f64, &2f64, &0.5).unwrap_err().failing, 1);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn assertion_message_reports_ndarray_coord() {
+    let actual = arr3(&[[[1f64, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]]);
+    let expected = actual.mapv(|v| if v == 7.0 { 7.5 } else { v });
+    let message = panic_message(|| assert_appro_eq!(actual, expected, 0.1));
+    assert!(message.contains("error: error `0.5` exceeds tolerance `0.1` at [1, 1, 0]"));
+}
+
+#[test]
+fn assertion_message_includes_custom_message() {
+    let seed = 42;
+    let message = panic_message(|| assert_appro_eq!(1f64, 2f64, msg = "seed: {}", seed));
+    assert!(message.starts_with("assertion failed: `(left == right)`: seed: 42\n"));
+    assert!(message.contains("error: error `1.0` exceeds default tolerance `1e-11`"));
+    let message = panic_message(|| assert_appro_eq!(1f64, 2f64, 0.5, msg = "seed: {}", seed));
+    assert!(message.starts_with("assertion failed: `(left == right)`: seed: 42\n"));
+    assert!(message.contains("error: error `1.0` exceeds tolerance `0.5`"));
+    let message = panic_message(|| {
+        assert_appro_eq!(
+            1f64,
+            2f64,
+            tol = Tol::new().abs(0.5),
+            msg = "n = {n}",
+            n = 3
+        )
+    });
+    assert!(message.starts_with("assertion failed: `(left == right)`: n = 3\n"));
+    let message = panic_message(|| {
+        assert_appro_eq_abs!(f64::NAN, 2f64, policy = NAN_EQ, msg = "with policy")
+    });
+    assert!(message.starts_with("assertion failed: `(left == right)`: with policy\n"));
+    let message = panic_message(|| {
+        assert_appro_eq_rel!(1f64, 2f64, 0.1, RelErrorDef::Max, msg = "seed: {}", seed)
+    });
+    assert!(message.contains("seed: 42"));
+    let message = panic_message(|| assert_appro_eq_rel!(1f64, 2f64, msg = "{}", "default"));
+    assert!(message.contains(": default\n"));
+    let message = panic_message(|| assert_appro_eq_ulps!(1f64, 2f64, 4u64, msg = "ulps"));
+    assert!(message.contains(": ulps\n"));
+    let message = panic_message(|| assert_appro_eq_mixed!(1f64, 2f64, 0.1, 0.1, msg = "mixed"));
+    assert!(message.contains(": mixed\n"));
+}
+
+#[test]
+fn assertion_message_includes_format_string() {
+    let seed = 42;
+    let message = panic_message(|| assert_appro_eq!(1f64, 2f64, 0.5, "seed: {}", seed));
+    assert!(message.starts_with("assertion failed: `(left == right)`: seed: 42\n"));
+    assert!(message.contains("error: error `1.0` exceeds tolerance `0.5`"));
+    let message =
+        panic_message(|| assert_appro_eq!(1f64, 2f64, tol = Tol::new().abs(0.5), "n = {n}", n = 3));
+    assert!(message.starts_with("assertion failed: `(left == right)`: n = 3\n"));
+    let message =
+        panic_message(|| assert_appro_eq_abs!(f64::NAN, 2f64, policy = NAN_EQ, "with policy"));
+    assert!(message.starts_with("assertion failed: `(left == right)`: with policy\n"));
+    let message = panic_message(|| assert_appro_eq_rel!(1f64, 2f64, 0.1, "seed: {}", seed));
+    assert!(message.contains(": seed: 42\n"));
+    let message =
+        panic_message(|| assert_appro_eq_rel!(1f64, 2f64, 0.1, RelErrorDef::Max, "seed: {}", seed));
+    assert!(message.contains(": seed: 42\n"));
+    let message = panic_message(|| assert_appro_eq_ulps!(1f64, 2f64, 4u64, "ulps",));
+    assert!(message.contains(": ulps\n"));
+    let message = panic_message(|| assert_appro_eq_mixed!(1f64, 2f64, 0.1, 0.1, "mixed"));
+    assert!(message.contains(": mixed\n"));
+    let message = panic_message(|| assert_appro_ne!(1f64, 1f64, 0.5, "step {}", 1));
+    assert!(message.starts_with("assertion failed: `(left != right)`: step 1\n"));
+}
+
+ok_test_none!(
+    compare_with_format_string,
+    1f64,
+    1.5f64,
+    0.6f64,
+    "seed: {}",
+    42
+);
+ok_test_none!(compare_with_msg, 1f64, 1.5f64, 0.6f64, msg = "seed: {}", 42);
+ok_test_none!(
+    compare_with_default_and_msg,
+    0f64,
+    1e-12f64,
+    msg = "no args"
+);
+
+#[test]
+fn assert_appro_ne_passes_when_not_equal() {
+    assert_appro_ne!(1f64, 2f64);
+    assert_appro_ne!(1f64, 1.5f64, 0.4);
+    assert_appro_ne!(1f64, 1.5f64, tol = Tol::new().rel(0.3));
+    assert_appro_ne!(vec![1f64, 2.0], vec![1f64, 2.0, 3.0]);
+    assert_appro_ne!([1f64, f64::NAN], [1f64, f64::NAN]);
+    assert_appro_ne!(f64::INFINITY, f64::INFINITY);
+    assert_appro_ne_abs!(vec![1f64, 2.0], vec![1f64, 2.1], 0.05, msg = "step {}", 1);
+    assert_appro_ne_rel!(100f64, 101f64, 0.005);
+    assert_appro_ne_rel!(0i32, 1i32);
+    assert_appro_ne_rel!(1f64, -3f64, 1.3, RelErrorDef::Max);
+    debug_assert_appro_ne!(1f64, 2f64);
+    debug_assert_appro_ne_abs!(1f64, 2f64);
+    debug_assert_appro_ne_rel!(1f64, 2f64);
+}
+
+#[test]
+fn assert_appro_ne_message() {
+    let message = panic_message(|| assert_appro_ne!(1f64, 1f64));
+    assert!(message.starts_with("assertion failed: `(left != right)`\n"));
+    assert!(message.contains("error: error `0.0` is within default tolerance `1e-11`"));
+    let message = panic_message(|| {
+        assert_appro_ne_abs!(vec![1f64, 2.0], vec![1f64, 2.1], 0.5, msg = "step {}", 1)
+    });
+    assert!(message.starts_with("assertion failed: `(left != right)`: step 1\n"));
+    assert!(message.contains("error: error `0.10000000000000009` is within tolerance `0.5` at [1]"));
+    let message = panic_message(|| assert_appro_ne_rel!(Vec::<f64>::new(), Vec::<f64>::new()));
+    assert!(message.contains("error: no error measured, default tolerance `1e-11`"));
+}
+
+#[test]
+#[should_panic]
+fn bad_assert_appro_ne_rel_with_def() {
+    assert_appro_ne_rel!(1f64, -3f64, 1.4, RelErrorDef::Max);
+}
+
+#[test]
+#[should_panic]
+fn bad_assert_appro_ne_with_policy() {
+    assert_appro_ne!(f64::NAN, f64::NAN, policy = NAN_EQ);
+}